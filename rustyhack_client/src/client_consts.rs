@@ -9,7 +9,11 @@ pub(crate) const TARGET_FPS: u32 = 10;
 pub(crate) const LOG_NAME: &str = "rustyhack_client.log";
 pub(crate) const GAME_TITLE: &str = "Rustyhack MMO";
 pub(crate) const VALID_NAME_REGEX: &str = "^[[:alpha:]]+$";
-pub(crate) const NON_COLLIDABLE_OBJECTS: [char; 5] = [',', ' ', '/', '>', '<'];
+pub(crate) const NON_COLLIDABLE_OBJECTS: [char; 10] =
+    [',', ' ', '/', '>', '<', '~', '=', '"', '^', 'O'];
 pub(crate) const CLIENT_CLEANUP_TICK: Duration = Duration::from_secs(10);
+pub(crate) const COMBAT_TIMEOUT: Duration = Duration::from_secs(5);
 pub(crate) const DEFAULT_BG_COLOUR: Color = Color::Reset;
 pub(crate) const DEFAULT_FG_COLOUR: Color = Color::Reset;
+pub(crate) const EXPLORED_NOT_VISIBLE_COLOUR: Color = Color::DarkGrey;
+pub(crate) const MAP_EXIT_COLOUR: Color = Color::Cyan;
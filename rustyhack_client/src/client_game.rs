@@ -1,4 +1,5 @@
 mod client_updates_handler;
+mod combat_statistics;
 mod input;
 mod screens;
 
@@ -6,7 +7,9 @@ use console_engine::{ConsoleEngine, KeyCode, KeyEventKind, KeyModifiers};
 use crossbeam_channel::{Receiver, Sender};
 use crossterm::style::Color;
 use laminar::{Packet, SocketEvent};
-use rustyhack_lib::network::packets::EntityPositionBroadcast;
+use rustyhack_lib::ecs::ability::AllAbilities;
+use rustyhack_lib::ecs::components::{ExploredMaps, StatusEffects};
+use rustyhack_lib::network::packets::{EntityPositionBroadcast, MapExitsBroadcast};
 use std::collections::HashMap;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -14,6 +17,7 @@ use std::time::{Duration, Instant};
 use crate::client_consts::{
     CLIENT_CLEANUP_TICK, GAME_TITLE, INITIAL_CONSOLE_HEIGHT, INITIAL_CONSOLE_WIDTH, TARGET_FPS,
 };
+use crate::client_game::combat_statistics::CombatStatistics;
 use crate::client_game::screens::{SidebarState, draw_screens};
 use input::commands::movement;
 
@@ -38,7 +42,7 @@ pub(super) fn run(
     );
 
     //get basic data from server needed to start client_game
-    let all_maps = map_downloader::request_all_maps_data(server_tcp_addr);
+    let mut all_maps = map_downloader::request_all_maps_data(server_tcp_addr);
 
     //create player
     let mut player = new_player::send_new_player_request(
@@ -56,7 +60,12 @@ pub(super) fn run(
     info!("Initialised console engine.");
 
     let mut entity_position_map: EntityPositionBroadcast = HashMap::new();
+    let mut explored_maps = ExploredMaps::default();
+    let mut map_exits: MapExitsBroadcast = HashMap::new();
     let mut system_messages: Vec<(String, Color)> = vec![];
+    let mut all_abilities: AllAbilities = vec![];
+    let mut status_effects = StatusEffects::default();
+    let mut combat_statistics = CombatStatistics::new();
     let mut sidebar_state = SidebarState::StatusBar;
 
     info!("Starting client_game loop");
@@ -66,14 +75,20 @@ pub(super) fn run(
         console.wait_frame();
 
         debug!("About to send player velocity update.");
-        movement::send_player_updates(sender, &console, &mut player, server_addr);
+        movement::send_player_updates(sender, &console, &mut player, server_addr, sidebar_state);
 
         debug!("About to wait for entity updates from server.");
         client_updates_handler::handle_received_server_messages(
             &player_update_receiver,
             &mut player,
+            &mut all_maps,
+            &mut explored_maps,
+            &mut map_exits,
             &mut entity_position_map,
             &mut system_messages,
+            &mut all_abilities,
+            &mut status_effects,
+            &mut combat_statistics,
         );
 
         if client_cleanup_tick_time.elapsed() > CLIENT_CLEANUP_TICK {
@@ -91,7 +106,9 @@ pub(super) fn run(
             &mut system_messages,
             &player,
             &all_maps,
+            &explored_maps,
             &entity_position_map,
+            &all_abilities,
             server_addr,
             sidebar_state,
         );
@@ -101,7 +118,12 @@ pub(super) fn run(
             &mut console,
             &all_maps,
             &player,
+            &explored_maps,
+            &map_exits,
             &entity_position_map,
+            &all_abilities,
+            &status_effects,
+            &combat_statistics,
             &system_messages,
             sidebar_state,
         );
@@ -1,15 +1,26 @@
 use crate::client_consts::DEFAULT_FG_COLOUR;
+use crate::client_game::combat_statistics::CombatStatistics;
 use chrono::{DateTime, Local};
 use crossbeam_channel::Receiver;
 use crossterm::style::Color;
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::ability::AllAbilities;
+use rustyhack_lib::ecs::components::{ExploredMaps, StatusEffects};
 use rustyhack_lib::ecs::player::Player;
-use rustyhack_lib::network::packets::{EntityPositionBroadcast, ServerMessage};
+use rustyhack_lib::network::packets::{EntityPositionBroadcast, MapExitsBroadcast, ServerMessage};
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn handle_received_server_messages(
     channel_receiver: &Receiver<ServerMessage>,
     player: &mut Player,
+    all_maps: &mut AllMaps,
+    explored_maps: &mut ExploredMaps,
+    map_exits: &mut MapExitsBroadcast,
     entity_position_broadcast: &mut EntityPositionBroadcast,
     status_messages: &mut Vec<(String, Color)>,
+    all_abilities: &mut AllAbilities,
+    status_effects: &mut StatusEffects,
+    combat_statistics: &mut CombatStatistics,
 ) {
     debug!("Checking for received messages from server.");
     while !channel_receiver.is_empty() {
@@ -28,6 +39,14 @@ pub(super) fn handle_received_server_messages(
                     debug!("Player stats update received: {:?}", &new_inventory);
                     player.inventory = new_inventory.clone();
                 }
+                ServerMessage::UpdateStatusEffects(new_status_effects) => {
+                    debug!("Player status effects update received: {new_status_effects:?}");
+                    *status_effects = new_status_effects;
+                }
+                ServerMessage::CombatEvent(combat_event) => {
+                    debug!("Combat event received: {combat_event:?}");
+                    combat_statistics.record(&combat_event, &player.player_details.player_name);
+                }
                 ServerMessage::SystemMessage(message) => {
                     debug!("System message received: {:?}", &message);
                     let date_time: DateTime<Local> = Local::now();
@@ -37,6 +56,50 @@ pub(super) fn handle_received_server_messages(
                         message.colour.unwrap_or(DEFAULT_FG_COLOUR),
                     ));
                 }
+                ServerMessage::UpdateExploredTiles((map, new_explored_tiles)) => {
+                    debug!(
+                        "Player explored {} new tiles on {}.",
+                        new_explored_tiles.len(),
+                        &map
+                    );
+                    explored_maps
+                        .maps
+                        .entry(map)
+                        .or_default()
+                        .extend(new_explored_tiles);
+                }
+                ServerMessage::UpdateDiscoveredTraps((map, new_discovered_traps)) => {
+                    debug!(
+                        "Player discovered {} traps on {}.",
+                        new_discovered_traps.len(),
+                        &map
+                    );
+                    explored_maps
+                        .discovered_traps
+                        .entry(map)
+                        .or_default()
+                        .extend(new_discovered_traps);
+                }
+                ServerMessage::UpdateTiles((map, new_tiles)) => {
+                    debug!("Received {} changed tiles on {}.", new_tiles.len(), &map);
+                    if let Some(background_map) = all_maps.get_mut(&map) {
+                        for (x, y, new_tile) in new_tiles {
+                            if let Some(tile) =
+                                background_map.data.get_mut((y as usize, x as usize))
+                            {
+                                *tile = new_tile;
+                            }
+                        }
+                    }
+                }
+                ServerMessage::UpdateMapExits((map, new_map_exits)) => {
+                    debug!("Received {} map exits for {}.", new_map_exits.len(), &map);
+                    map_exits.insert(map, new_map_exits);
+                }
+                ServerMessage::AbilityDefinitions(new_abilities) => {
+                    debug!("Received {} ability definitions.", new_abilities.len());
+                    *all_abilities = new_abilities;
+                }
                 ServerMessage::UpdateOtherEntities(new_update) => {
                     debug!("Entity position broadcast received: {:?}", &new_update);
                     entity_position_broadcast.insert(new_update.0, new_update.1);
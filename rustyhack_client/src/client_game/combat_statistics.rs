@@ -0,0 +1,100 @@
+use crate::client_consts::COMBAT_TIMEOUT;
+use rustyhack_lib::network::packets::{AttackOutcome, CombatEvent};
+use std::time::{Duration, Instant};
+
+//totals for this session only, built up from the combat events the server sends us
+pub(super) struct CombatStatistics {
+    pub(super) session_start: Instant,
+    pub(super) time_in_combat: Duration,
+    last_combat_time: Option<Instant>,
+    pub(super) attacks_made: u32,
+    pub(super) hits: u32,
+    pub(super) critical_hits: u32,
+    pub(super) damage_dealt: f32,
+    pub(super) highest_hit: f32,
+    pub(super) kills: u32,
+    pub(super) attacks_received: u32,
+    pub(super) attacks_blocked: u32,
+    pub(super) damage_taken: f32,
+    pub(super) deaths: u32,
+}
+
+impl CombatStatistics {
+    pub(super) fn new() -> Self {
+        CombatStatistics {
+            session_start: Instant::now(),
+            time_in_combat: Duration::ZERO,
+            last_combat_time: None,
+            attacks_made: 0,
+            hits: 0,
+            critical_hits: 0,
+            damage_dealt: 0.0,
+            highest_hit: 0.0,
+            kills: 0,
+            attacks_received: 0,
+            attacks_blocked: 0,
+            damage_taken: 0.0,
+            deaths: 0,
+        }
+    }
+
+    pub(super) fn record(&mut self, combat_event: &CombatEvent, player_name: &str) {
+        self.update_time_in_combat();
+        let damage = combat_event.outcome.damage();
+        if combat_event.attacker == player_name {
+            self.attacks_made += 1;
+            if let AttackOutcome::Hit { critical, .. } = combat_event.outcome {
+                self.hits += 1;
+                if critical {
+                    self.critical_hits += 1;
+                }
+            }
+            self.damage_dealt += damage;
+            self.highest_hit = self.highest_hit.max(damage);
+            if combat_event.killed {
+                self.kills += 1;
+            }
+        }
+        if combat_event.defender == player_name {
+            self.attacks_received += 1;
+            if combat_event.outcome == AttackOutcome::Blocked {
+                self.attacks_blocked += 1;
+            }
+            self.damage_taken += damage;
+            if combat_event.killed {
+                self.deaths += 1;
+            }
+        }
+    }
+
+    //gaps between fights don't count towards dps
+    fn update_time_in_combat(&mut self) {
+        let now = Instant::now();
+        if let Some(last_combat_time) = self.last_combat_time {
+            let since_last_combat = now.duration_since(last_combat_time);
+            if since_last_combat < COMBAT_TIMEOUT {
+                self.time_in_combat += since_last_combat;
+            }
+        }
+        self.last_combat_time = Some(now);
+    }
+
+    pub(super) fn damage_per_second(&self) -> f32 {
+        self.damage_dealt / self.time_in_combat.as_secs_f32().max(1.0)
+    }
+
+    pub(super) fn hit_rate_percentage(&self) -> f64 {
+        percentage(self.hits, self.attacks_made)
+    }
+
+    pub(super) fn block_rate_percentage(&self) -> f64 {
+        percentage(self.attacks_blocked, self.attacks_received)
+    }
+}
+
+fn percentage(count: u32, total: u32) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    f64::from(count) * 100.0 / f64::from(total)
+}
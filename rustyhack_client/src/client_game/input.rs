@@ -8,6 +8,9 @@ use crossbeam_channel::Sender;
 use crossterm::style::Color;
 use laminar::Packet;
 use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::ability::AllAbilities;
+use rustyhack_lib::ecs::components::ExploredMaps;
+use rustyhack_lib::ecs::item::get_ranged_weapon;
 use rustyhack_lib::ecs::player::Player;
 use rustyhack_lib::network::packets::EntityPositionBroadcast;
 
@@ -18,7 +21,9 @@ pub(super) fn handle_other_input(
     system_messages: &mut Vec<(String, Color)>,
     player: &Player,
     all_maps: &AllMaps,
+    explored_maps: &ExploredMaps,
     entity_position_map: &EntityPositionBroadcast,
+    all_abilities: &AllAbilities,
     server_addr: &str,
     mut sidebar_state: SidebarState,
 ) -> SidebarState {
@@ -27,6 +32,7 @@ pub(super) fn handle_other_input(
         system_messages,
         player,
         all_maps,
+        explored_maps,
         entity_position_map,
         sender,
         server_addr,
@@ -34,7 +40,17 @@ pub(super) fn handle_other_input(
 
     match sidebar_state {
         SidebarState::StatusBar => {
-            sidebar_state = sidebar_state_check(console, system_messages, player, sidebar_state);
+            sidebar_state = sidebar_state_check(
+                console,
+                system_messages,
+                player,
+                all_maps,
+                entity_position_map,
+                all_abilities,
+                sender,
+                server_addr,
+                sidebar_state,
+            );
         }
         SidebarState::DropItemChoice(item_page_index) => {
             sidebar_state = commands::drop::drop_item_choice(
@@ -46,6 +62,59 @@ pub(super) fn handle_other_input(
                 item_page_index,
             );
         }
+        SidebarState::FireTargetChoice(target_index) => {
+            sidebar_state = commands::fire::fire_target_choice(
+                console,
+                system_messages,
+                player,
+                all_maps,
+                entity_position_map,
+                sender,
+                server_addr,
+                target_index,
+            );
+        }
+        SidebarState::AbilityChoice => {
+            sidebar_state = commands::ability::ability_choice(
+                console,
+                system_messages,
+                player,
+                all_abilities,
+                all_maps,
+                entity_position_map,
+                sender,
+                server_addr,
+            );
+        }
+        SidebarState::AbilityTargetChoice(ability_index, target_index) => {
+            sidebar_state = commands::ability::ability_target_choice(
+                console,
+                system_messages,
+                player,
+                all_abilities,
+                all_maps,
+                entity_position_map,
+                sender,
+                server_addr,
+                ability_index,
+                target_index,
+            );
+        }
+        SidebarState::MapOverview => {
+            sidebar_state = check_for_close(console, 'w', sidebar_state);
+        }
+        SidebarState::CombatStatistics => {
+            sidebar_state = check_for_close(console, 'c', sidebar_state);
+        }
+        SidebarState::BuildBarricadeChoice => {
+            sidebar_state = commands::build_barricade::build_barricade_choice(
+                console,
+                player,
+                sender,
+                server_addr,
+                sidebar_state,
+            );
+        }
         SidebarState::StatUpChoice => {
             sidebar_state = commands::stat_up::stat_up_choice(
                 console,
@@ -64,10 +133,30 @@ fn check_for_escape(console: &ConsoleEngine) -> bool {
     console.is_key_pressed(KeyCode::Esc)
 }
 
+//screens with nothing to choose are closed with escape or the same key that opened them
+fn check_for_close(
+    console: &ConsoleEngine,
+    open_key: char,
+    sidebar_state: SidebarState,
+) -> SidebarState {
+    if check_for_escape(console) || console.is_key_pressed(KeyCode::Char(open_key)) {
+        info!("Returning to default sidebar window.");
+        SidebarState::StatusBar
+    } else {
+        sidebar_state
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn sidebar_state_check(
     console: &mut ConsoleEngine,
     system_messages: &mut Vec<(String, Color)>,
     player: &Player,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+    all_abilities: &AllAbilities,
+    sender: &Sender<Packet>,
+    server_addr: &str,
     mut sidebar_state: SidebarState,
 ) -> SidebarState {
     if console.is_key_pressed(KeyCode::Char('d')) {
@@ -83,15 +172,55 @@ fn sidebar_state_check(
     } else if console.is_key_pressed(KeyCode::Char('u')) && player.stats.stat_points > 0 {
         info!("Stat up command pressed.");
         sidebar_state = SidebarState::StatUpChoice;
+    } else if console.is_key_pressed(KeyCode::Char('w')) {
+        info!("Map overview command pressed.");
+        sidebar_state = SidebarState::MapOverview;
+    } else if console.is_key_pressed(KeyCode::Char('c')) {
+        info!("Combat statistics command pressed.");
+        sidebar_state = SidebarState::CombatStatistics;
+    } else if console.is_key_pressed(KeyCode::Char('b')) {
+        info!("Build barricade command pressed.");
+        sidebar_state = SidebarState::BuildBarricadeChoice;
+    } else if console.is_key_pressed(KeyCode::Char('f')) {
+        info!("Fire command pressed.");
+        if get_ranged_weapon(&player.inventory).is_none() {
+            commands::fire::push_fire_message(system_messages, "You have nothing to fire.");
+        } else if commands::fire::get_ranged_targets(player, all_maps, entity_position_map)
+            .is_empty()
+        {
+            commands::fire::push_fire_message(
+                system_messages,
+                "There's nothing in range to shoot.",
+            );
+        } else {
+            sidebar_state = SidebarState::FireTargetChoice(0);
+        }
+    } else if console.is_key_pressed(KeyCode::Char('a')) {
+        info!("Ability command pressed.");
+        sidebar_state = SidebarState::AbilityChoice;
+    } else if let Some(ability_index) = commands::ability::check_for_ability_hotkey(console) {
+        info!("Ability hotkey pressed.");
+        sidebar_state = commands::ability::use_ability(
+            system_messages,
+            player,
+            all_abilities,
+            all_maps,
+            entity_position_map,
+            sender,
+            server_addr,
+            ability_index,
+        );
     }
     sidebar_state
 }
 
+#[allow(clippy::too_many_arguments)]
 fn default_input_check(
     console: &mut ConsoleEngine,
     system_messages: &mut Vec<(String, Color)>,
     player: &Player,
     all_maps: &AllMaps,
+    explored_maps: &ExploredMaps,
     entity_position_map: &EntityPositionBroadcast,
     sender: &Sender<Packet>,
     server_addr: &str,
@@ -101,6 +230,7 @@ fn default_input_check(
             system_messages,
             player,
             all_maps,
+            explored_maps,
             entity_position_map,
         );
     } else if console.is_key_pressed(KeyCode::Char('p')) {
@@ -115,5 +245,11 @@ fn default_input_check(
     } else if console.is_key_pressed(KeyCode::Char('m')) {
         info!("Change map command pressed.");
         commands::change_map::send_change_map_request(sender, player, server_addr);
+    } else if console.is_key_pressed(KeyCode::Char('s')) {
+        info!("Search command pressed.");
+        commands::search::send_search_request(sender, player, server_addr);
+    } else if console.is_key_pressed(KeyCode::Char('v')) {
+        info!("Toggle PvP command pressed.");
+        commands::toggle_pvp::send_toggle_pvp_request(sender, player, server_addr);
     }
 }
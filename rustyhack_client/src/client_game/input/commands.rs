@@ -1,6 +1,11 @@
+pub(super) mod ability;
+pub(super) mod build_barricade;
 pub(super) mod change_map;
 pub(super) mod drop;
+pub(crate) mod fire;
 pub(super) mod look;
 pub(crate) mod movement;
 pub(super) mod pickup;
+pub(super) mod search;
 pub(super) mod stat_up;
+pub(super) mod toggle_pvp;
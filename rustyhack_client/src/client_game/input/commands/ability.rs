@@ -0,0 +1,158 @@
+use crate::client_game::input;
+use crate::client_game::input::commands::fire;
+use crate::client_game::screens::SidebarState;
+use bincode::{config, encode_to_vec};
+use console_engine::ConsoleEngine;
+use crossbeam_channel::Sender;
+use crossterm::event::KeyCode;
+use crossterm::style::Color;
+use laminar::Packet;
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::ability::{AllAbilities, get_known_abilities};
+use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::network::packets::{EntityPositionBroadcast, PlayerRequest};
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn ability_choice(
+    console: &mut ConsoleEngine,
+    system_messages: &mut Vec<(String, Color)>,
+    player: &Player,
+    all_abilities: &AllAbilities,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+    sender: &Sender<Packet>,
+    server_addr: &str,
+) -> SidebarState {
+    if input::check_for_escape(console) {
+        info!("Returning to default sidebar window.");
+        return SidebarState::StatusBar;
+    }
+    for (index, key) in ('1'..='9').enumerate() {
+        if console.is_key_pressed(KeyCode::Char(key)) {
+            return use_ability(
+                system_messages,
+                player,
+                all_abilities,
+                all_maps,
+                entity_position_map,
+                sender,
+                server_addr,
+                index,
+            );
+        }
+    }
+    SidebarState::AbilityChoice
+}
+
+//the same slots as the ability list, so F1 uses whatever (1) would in the list
+pub(crate) fn check_for_ability_hotkey(console: &ConsoleEngine) -> Option<usize> {
+    (1..=9).find_map(|number: u8| {
+        console
+            .is_key_pressed(KeyCode::F(number))
+            .then(|| usize::from(number - 1))
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn use_ability(
+    system_messages: &mut Vec<(String, Color)>,
+    player: &Player,
+    all_abilities: &AllAbilities,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+    sender: &Sender<Packet>,
+    server_addr: &str,
+    ability_index: usize,
+) -> SidebarState {
+    let known_abilities = get_known_abilities(all_abilities, player.stats.level);
+    let Some(ability) = known_abilities.get(ability_index) else {
+        fire::push_fire_message(system_messages, "You don't know an ability in that slot.");
+        return SidebarState::StatusBar;
+    };
+    info!("Ability {} chosen.", ability.name);
+    let Some(range) = ability.target_range() else {
+        send_use_ability_request(sender, player, server_addr, &ability.name, None);
+        return SidebarState::StatusBar;
+    };
+    if fire::get_targets_in_range(player, all_maps, entity_position_map, range).is_empty() {
+        fire::push_fire_message(system_messages, "There's nothing in range to target.");
+        SidebarState::StatusBar
+    } else {
+        SidebarState::AbilityTargetChoice(u16::try_from(ability_index).unwrap_or(0), 0)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn ability_target_choice(
+    console: &mut ConsoleEngine,
+    system_messages: &mut Vec<(String, Color)>,
+    player: &Player,
+    all_abilities: &AllAbilities,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+    sender: &Sender<Packet>,
+    server_addr: &str,
+    ability_index: u16,
+    target_index: u16,
+) -> SidebarState {
+    if input::check_for_escape(console) {
+        info!("Returning to default sidebar window.");
+        return SidebarState::StatusBar;
+    }
+    let known_abilities = get_known_abilities(all_abilities, player.stats.level);
+    let Some(ability) = known_abilities.get(usize::from(ability_index)) else {
+        return SidebarState::StatusBar;
+    };
+    let targets = fire::get_targets_in_range(
+        player,
+        all_maps,
+        entity_position_map,
+        ability.target_range().unwrap_or(0),
+    );
+    if targets.is_empty() {
+        fire::push_fire_message(system_messages, "There's nothing in range to target.");
+        return SidebarState::StatusBar;
+    }
+    let target_index = usize::from(target_index) % targets.len();
+    if console.is_key_pressed(KeyCode::Tab) {
+        let next_index = (target_index + 1) % targets.len();
+        SidebarState::AbilityTargetChoice(ability_index, u16::try_from(next_index).unwrap_or(0))
+    } else if console.is_key_pressed(KeyCode::Enter) {
+        send_use_ability_request(
+            sender,
+            player,
+            server_addr,
+            &ability.name,
+            Some(targets[target_index].position),
+        );
+        SidebarState::StatusBar
+    } else {
+        SidebarState::AbilityTargetChoice(ability_index, u16::try_from(target_index).unwrap_or(0))
+    }
+}
+
+fn send_use_ability_request(
+    sender: &Sender<Packet>,
+    player: &Player,
+    server_addr: &str,
+    ability_name: &str,
+    target: Option<(u32, u32)>,
+) {
+    let packet = Packet::reliable_ordered(
+        server_addr
+            .parse()
+            .expect("Server address format is invalid."),
+        encode_to_vec(
+            PlayerRequest::UseAbility((
+                player.player_details.player_name.clone(),
+                ability_name.to_string(),
+                target,
+            )),
+            config::standard(),
+        )
+        .unwrap(),
+        Some(19),
+    );
+    rustyhack_lib::network::send_packet(packet, sender);
+    info!("Sent use ability request packet to server for {ability_name}.");
+}
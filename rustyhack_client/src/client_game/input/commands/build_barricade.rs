@@ -0,0 +1,73 @@
+use crate::client_game::input;
+use crate::client_game::screens::SidebarState;
+use bincode::{config, encode_to_vec};
+use console_engine::ConsoleEngine;
+use crossbeam_channel::Sender;
+use crossterm::event::KeyCode;
+use laminar::Packet;
+use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::network::packets::PlayerRequest;
+
+pub(crate) fn build_barricade_choice(
+    console: &mut ConsoleEngine,
+    player: &Player,
+    sender: &Sender<Packet>,
+    server_addr: &str,
+    mut sidebar_state: SidebarState,
+) -> SidebarState {
+    if input::check_for_escape(console) {
+        info!("Returning to default sidebar window.");
+        sidebar_state = SidebarState::StatusBar;
+    } else if let Some(direction) = check_for_direction(console) {
+        send_build_barricade_request(sender, player, server_addr, direction);
+        sidebar_state = SidebarState::StatusBar;
+    }
+    sidebar_state
+}
+
+fn send_build_barricade_request(
+    sender: &Sender<Packet>,
+    player: &Player,
+    server_addr: &str,
+    direction: (i32, i32),
+) {
+    let packet = Packet::reliable_ordered(
+        server_addr
+            .parse()
+            .expect("Server address format is invalid."),
+        encode_to_vec(
+            PlayerRequest::BuildBarricade((
+                player.player_details.player_name.clone(),
+                direction.0,
+                direction.1,
+            )),
+            config::standard(),
+        )
+        .unwrap(),
+        Some(17),
+    );
+    rustyhack_lib::network::send_packet(packet, sender);
+    info!("Sent build barricade request packet to server.");
+}
+
+fn check_for_direction(console: &ConsoleEngine) -> Option<(i32, i32)> {
+    if console.is_key_pressed(KeyCode::Char('7')) {
+        Some((-1, -1))
+    } else if console.is_key_pressed(KeyCode::Char('8')) {
+        Some((0, -1))
+    } else if console.is_key_pressed(KeyCode::Char('9')) {
+        Some((1, -1))
+    } else if console.is_key_pressed(KeyCode::Char('4')) {
+        Some((-1, 0))
+    } else if console.is_key_pressed(KeyCode::Char('6')) {
+        Some((1, 0))
+    } else if console.is_key_pressed(KeyCode::Char('1')) {
+        Some((-1, 1))
+    } else if console.is_key_pressed(KeyCode::Char('2')) {
+        Some((0, 1))
+    } else if console.is_key_pressed(KeyCode::Char('3')) {
+        Some((1, 1))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,146 @@
+use crate::client_consts::DEFAULT_FG_COLOUR;
+use crate::client_game::input;
+use crate::client_game::screens::SidebarState;
+use bincode::{config, encode_to_vec};
+use chrono::{DateTime, Local};
+use console_engine::ConsoleEngine;
+use crossbeam_channel::Sender;
+use crossterm::event::KeyCode;
+use crossterm::style::Color;
+use laminar::Packet;
+use rustyhack_lib::background_map::{AllMaps, field_of_view, lighting};
+use rustyhack_lib::consts::{DEFAULT_ITEM_ICON, DEFAULT_PLAYER_VIEW_DISTANCE};
+use rustyhack_lib::ecs::item::{get_carried_light_radius, get_ranged_weapon};
+use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::network::packets::{EntityPositionBroadcast, PlayerRequest};
+use std::collections::HashSet;
+
+pub(crate) struct RangedTarget {
+    pub(crate) name: String,
+    pub(crate) position: (u32, u32),
+    pub(crate) distance: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn fire_target_choice(
+    console: &mut ConsoleEngine,
+    system_messages: &mut Vec<(String, Color)>,
+    player: &Player,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+    sender: &Sender<Packet>,
+    server_addr: &str,
+    target_index: u16,
+) -> SidebarState {
+    if input::check_for_escape(console) {
+        info!("Returning to default sidebar window.");
+        return SidebarState::StatusBar;
+    }
+    let targets = get_ranged_targets(player, all_maps, entity_position_map);
+    if targets.is_empty() {
+        push_fire_message(system_messages, "There's nothing in range to shoot.");
+        return SidebarState::StatusBar;
+    }
+    //targets come and go as they move, so keep the selection within the current list
+    let target_index = usize::from(target_index) % targets.len();
+    if console.is_key_pressed(KeyCode::Tab) {
+        let next_index = (target_index + 1) % targets.len();
+        SidebarState::FireTargetChoice(u16::try_from(next_index).unwrap_or(0))
+    } else if console.is_key_pressed(KeyCode::Enter) || console.is_key_pressed(KeyCode::Char('f')) {
+        send_ranged_attack_request(sender, player, server_addr, targets[target_index].position);
+        SidebarState::StatusBar
+    } else {
+        SidebarState::FireTargetChoice(u16::try_from(target_index).unwrap_or(0))
+    }
+}
+
+pub(crate) fn get_ranged_targets(
+    player: &Player,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+) -> Vec<RangedTarget> {
+    get_ranged_weapon(&player.inventory).map_or_else(Vec::new, |ranged_weapon| {
+        get_targets_in_range(player, all_maps, entity_position_map, ranged_weapon.range)
+    })
+}
+
+//hostility is only known to the server, so anything visible that isn't an item can be targeted
+pub(crate) fn get_targets_in_range(
+    player: &Player,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+    range: u32,
+) -> Vec<RangedTarget> {
+    let Some(current_map) = all_maps.get(&player.position.current_map) else {
+        return vec![];
+    };
+    let player_tile = (player.position.pos_x, player.position.pos_y);
+    let visible_tiles: HashSet<(u32, u32)> = lighting::get_lit_visible_tiles(
+        current_map,
+        player.position.pos_x,
+        player.position.pos_y,
+        DEFAULT_PLAYER_VIEW_DISTANCE,
+        get_carried_light_radius(&player.inventory.carried),
+    )
+    .into_iter()
+    .collect();
+
+    let mut targets: Vec<RangedTarget> = entity_position_map
+        .values()
+        .filter(|(x, y, map, icon, _colour, name)| {
+            *map == player.position.current_map
+                && *name != player.player_details.player_name
+                && *icon != DEFAULT_ITEM_ICON
+                && visible_tiles.contains(&(*x, *y))
+        })
+        .map(|(x, y, _map, _icon, _colour, name)| RangedTarget {
+            name: name.clone(),
+            position: (*x, *y),
+            distance: get_distance(player_tile, (*x, *y)),
+        })
+        .filter(|target| {
+            target.distance > 0
+                && target.distance <= range
+                && field_of_view::has_line_of_sight(current_map, player_tile, target.position)
+        })
+        .collect();
+    targets.sort_by_key(|target| (target.distance, target.position.1, target.position.0));
+    targets
+}
+
+//matches how the server measures range, so diagonals count as a single tile
+fn get_distance(from: (u32, u32), to: (u32, u32)) -> u32 {
+    from.0.abs_diff(to.0).max(from.1.abs_diff(to.1))
+}
+
+pub(crate) fn push_fire_message(system_messages: &mut Vec<(String, Color)>, message: &str) {
+    let date_time: DateTime<Local> = Local::now();
+    let time = date_time.format("[%H:%M:%S] ").to_string();
+    info!("{message}");
+    system_messages.push(((time + message), DEFAULT_FG_COLOUR));
+}
+
+fn send_ranged_attack_request(
+    sender: &Sender<Packet>,
+    player: &Player,
+    server_addr: &str,
+    target: (u32, u32),
+) {
+    let packet = Packet::reliable_ordered(
+        server_addr
+            .parse()
+            .expect("Server address format is invalid."),
+        encode_to_vec(
+            PlayerRequest::RangedAttack((
+                player.player_details.player_name.clone(),
+                target.0,
+                target.1,
+            )),
+            config::standard(),
+        )
+        .unwrap(),
+        Some(18),
+    );
+    rustyhack_lib::network::send_packet(packet, sender);
+    info!("Sent ranged attack request packet to server.");
+}
@@ -2,7 +2,8 @@ use crate::client_consts::DEFAULT_FG_COLOUR;
 use chrono::{DateTime, Local};
 use crossterm::style::Color;
 use rayon::prelude::*;
-use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::background_map::{AllMaps, BackgroundMap};
+use rustyhack_lib::ecs::components::ExploredMaps;
 use rustyhack_lib::ecs::player::Player;
 use rustyhack_lib::network::packets::EntityPositionBroadcast;
 
@@ -10,27 +11,48 @@ pub(crate) fn get_what_player_sees(
     system_messages: &mut Vec<(String, Color)>,
     player: &Player,
     all_maps: &AllMaps,
+    explored_maps: &ExploredMaps,
     entity_position_map: &EntityPositionBroadcast,
 ) {
     let date_time: DateTime<Local> = Local::now();
     let time = date_time.format("[%H:%M:%S] ").to_string();
     let current_map = all_maps.get(&player.position.current_map).unwrap();
 
-    let mut underneath = current_map
-        .get_tile_at(player.position.pos_y, player.position.pos_x)
-        .to_string();
-    let mut north = current_map
-        .get_tile_at(player.position.pos_y - 1, player.position.pos_x)
-        .to_string();
-    let mut south = current_map
-        .get_tile_at(player.position.pos_y + 1, player.position.pos_x)
-        .to_string();
-    let mut east = current_map
-        .get_tile_at(player.position.pos_y, player.position.pos_x + 1)
-        .to_string();
-    let mut west = current_map
-        .get_tile_at(player.position.pos_y, player.position.pos_x - 1)
-        .to_string();
+    let mut underneath = get_known_tile_at(
+        current_map,
+        explored_maps,
+        player,
+        player.position.pos_x,
+        player.position.pos_y,
+    );
+    let mut north = get_known_tile_at(
+        current_map,
+        explored_maps,
+        player,
+        player.position.pos_x,
+        player.position.pos_y - 1,
+    );
+    let mut south = get_known_tile_at(
+        current_map,
+        explored_maps,
+        player,
+        player.position.pos_x,
+        player.position.pos_y + 1,
+    );
+    let mut east = get_known_tile_at(
+        current_map,
+        explored_maps,
+        player,
+        player.position.pos_x + 1,
+        player.position.pos_y,
+    );
+    let mut west = get_known_tile_at(
+        current_map,
+        explored_maps,
+        player,
+        player.position.pos_x - 1,
+        player.position.pos_y,
+    );
 
     underneath = return_visible_entity_at(
         underneath,
@@ -80,6 +102,22 @@ pub(crate) fn get_what_player_sees(
     system_messages.push(((time + "West: " + &*west), DEFAULT_FG_COLOUR));
 }
 
+fn get_known_tile_at(
+    current_map: &BackgroundMap,
+    explored_maps: &ExploredMaps,
+    player: &Player,
+    x: u32,
+    y: u32,
+) -> String {
+    explored_maps
+        .discovered_traps
+        .get(&player.position.current_map)
+        .map_or(*current_map.get_tile_at(y, x), |discovered_traps| {
+            current_map.get_known_tile_at(y, x, discovered_traps)
+        })
+        .to_string()
+}
+
 pub(super) fn return_visible_entity_at(
     entity_name: String,
     entity_position_map: &EntityPositionBroadcast,
@@ -1,26 +1,54 @@
+use crate::client_game::screens::SidebarState;
 use bincode::{config, encode_to_vec};
-use console_engine::ConsoleEngine;
+use console_engine::{ConsoleEngine, KeyEventKind, KeyModifiers};
 use crossbeam_channel::Sender;
 use crossterm::event::KeyCode;
 use laminar::Packet;
 use rustyhack_lib::ecs::player::Player;
 use rustyhack_lib::network::packets::{PlayerRequest, PositionMessage};
 
+//numpad keys, which send these when num lock is off
+const NAVIGATION_DIAGONAL_KEYS: [(KeyCode, i32, i32); 4] = [
+    (KeyCode::Home, -1, -1),
+    (KeyCode::PageUp, 1, -1),
+    (KeyCode::End, -1, 1),
+    (KeyCode::PageDown, 1, 1),
+];
+
+//numpad keys with num lock on, only used when no choice screen needs the number keys
+const NUMBER_KEYS: [(char, i32, i32); 8] = [
+    ('7', -1, -1),
+    ('8', 0, -1),
+    ('9', 1, -1),
+    ('4', -1, 0),
+    ('6', 1, 0),
+    ('1', -1, 1),
+    ('2', 0, 1),
+    ('3', 1, 1),
+];
+
+//vi-keys, held with shift as the lowercase letters are already used by other commands
+const VI_KEYS: [(char, i32, i32); 8] = [
+    ('Y', -1, -1),
+    ('K', 0, -1),
+    ('U', 1, -1),
+    ('H', -1, 0),
+    ('L', 1, 0),
+    ('B', -1, 1),
+    ('J', 0, 1),
+    ('N', 1, 1),
+];
+
 pub(crate) fn send_player_updates(
     sender: &Sender<Packet>,
     console: &ConsoleEngine,
     player: &mut Player,
     server_addr: &str,
+    sidebar_state: SidebarState,
 ) {
-    if console.is_key_held(KeyCode::Up) {
-        player.position.velocity_y = -1;
-    } else if console.is_key_held(KeyCode::Down) {
-        player.position.velocity_y = 1;
-    } else if console.is_key_held(KeyCode::Left) {
-        player.position.velocity_x = -1;
-    } else if console.is_key_held(KeyCode::Right) {
-        player.position.velocity_x = 1;
-    }
+    let (velocity_x, velocity_y) = get_movement_direction(console, sidebar_state);
+    player.position.velocity_x = velocity_x;
+    player.position.velocity_y = velocity_y;
 
     if player.position.velocity_y != 0 || player.position.velocity_x != 0 {
         debug!("Movement detected, sending velocity packet to server.");
@@ -30,6 +58,56 @@ pub(crate) fn send_player_updates(
     player.position.velocity_y = 0;
 }
 
+fn get_movement_direction(console: &ConsoleEngine, sidebar_state: SidebarState) -> (i32, i32) {
+    //arrow keys can be held together to move diagonally
+    let mut velocity_x = 0;
+    let mut velocity_y = 0;
+    if console.is_key_held(KeyCode::Up) {
+        velocity_y -= 1;
+    }
+    if console.is_key_held(KeyCode::Down) {
+        velocity_y += 1;
+    }
+    if console.is_key_held(KeyCode::Left) {
+        velocity_x -= 1;
+    }
+    if console.is_key_held(KeyCode::Right) {
+        velocity_x += 1;
+    }
+    if velocity_x != 0 || velocity_y != 0 {
+        return (velocity_x, velocity_y);
+    }
+
+    let number_keys_available = matches!(
+        sidebar_state,
+        SidebarState::StatusBar | SidebarState::MapOverview | SidebarState::CombatStatistics
+    );
+    NAVIGATION_DIAGONAL_KEYS
+        .iter()
+        .find(|(key, _, _)| console.is_key_held(*key))
+        .map(|(_, x, y)| (*x, *y))
+        .or_else(|| {
+            NUMBER_KEYS
+                .iter()
+                .filter(|_| number_keys_available)
+                .find(|(key, _, _)| console.is_key_held(KeyCode::Char(*key)))
+                .map(|(_, x, y)| (*x, *y))
+        })
+        .or_else(|| {
+            VI_KEYS
+                .iter()
+                .find(|(key, _, _)| {
+                    console.is_key_held_with_modifier(
+                        KeyCode::Char(*key),
+                        KeyModifiers::SHIFT,
+                        KeyEventKind::Press,
+                    )
+                })
+                .map(|(_, x, y)| (*x, *y))
+        })
+        .unwrap_or((0, 0))
+}
+
 fn send_velocity_packet(sender: &Sender<Packet>, server_addr: &str, player: &Player) {
     let packet = Packet::unreliable_sequenced(
         server_addr
@@ -0,0 +1,24 @@
+use bincode::{config, encode_to_vec};
+use crossbeam_channel::Sender;
+use laminar::Packet;
+use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::network::packets::{PlayerRequest, PositionMessage};
+
+pub(crate) fn send_search_request(sender: &Sender<Packet>, player: &Player, server_addr: &str) {
+    let packet = Packet::reliable_ordered(
+        server_addr
+            .parse()
+            .expect("Server address format is invalid."),
+        encode_to_vec(
+            PlayerRequest::Search(PositionMessage {
+                player_name: player.player_details.player_name.clone(),
+                position: player.position.clone(),
+            }),
+            config::standard(),
+        )
+        .unwrap(),
+        Some(16),
+    );
+    rustyhack_lib::network::send_packet(packet, sender);
+    info!("Sent search request packet to server.");
+}
@@ -0,0 +1,21 @@
+use bincode::{config, encode_to_vec};
+use crossbeam_channel::Sender;
+use laminar::Packet;
+use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::network::packets::PlayerRequest;
+
+pub(crate) fn send_toggle_pvp_request(sender: &Sender<Packet>, player: &Player, server_addr: &str) {
+    let packet = Packet::reliable_ordered(
+        server_addr
+            .parse()
+            .expect("Server address format is invalid."),
+        encode_to_vec(
+            PlayerRequest::TogglePvp(player.player_details.player_name.clone()),
+            config::standard(),
+        )
+        .unwrap(),
+        Some(20),
+    );
+    rustyhack_lib::network::send_packet(packet, sender);
+    info!("Sent toggle PvP request packet to server.");
+}
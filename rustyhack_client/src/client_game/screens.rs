@@ -1,16 +1,25 @@
+mod ability_choice;
+mod ability_target_choice;
 mod bottom_text_window;
+mod build_barricade_choice;
+mod combat_statistics;
 pub(crate) mod drop_item_choice;
+mod fire_target_choice;
+mod map_overview;
 mod side_status_bar;
 mod stat_up_choice;
 mod top_status_bar;
 mod viewport;
 
 use crate::client_consts::{VIEWPORT_HEIGHT_PERCENTAGE, VIEWPORT_WIDTH_PERCENTAGE};
+use crate::client_game::combat_statistics::CombatStatistics;
 use console_engine::ConsoleEngine;
 use crossterm::style::Color;
 use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::ability::AllAbilities;
+use rustyhack_lib::ecs::components::{ExploredMaps, StatusEffects};
 use rustyhack_lib::ecs::player::Player;
-use rustyhack_lib::network::packets::EntityPositionBroadcast;
+use rustyhack_lib::network::packets::{EntityPositionBroadcast, MapExitsBroadcast};
 use rustyhack_lib::utils::math::i32_from;
 use std::process;
 
@@ -18,14 +27,27 @@ use std::process;
 pub(crate) enum SidebarState {
     StatusBar,
     DropItemChoice(u16),
+    FireTargetChoice(u16),
+    AbilityChoice,
+    //ability index in the known abilities list, then target index
+    AbilityTargetChoice(u16, u16),
     StatUpChoice,
+    BuildBarricadeChoice,
+    CombatStatistics,
+    MapOverview,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn draw_screens(
     console: &mut ConsoleEngine,
     all_maps: &AllMaps,
     player: &Player,
+    explored_maps: &ExploredMaps,
+    map_exits: &MapExitsBroadcast,
     entity_position_broadcast: &EntityPositionBroadcast,
+    all_abilities: &AllAbilities,
+    status_effects: &StatusEffects,
+    combat_statistics: &CombatStatistics,
     system_messages: &[(String, Color)],
     sidebar_state: SidebarState,
 ) {
@@ -40,30 +62,92 @@ pub(super) fn draw_screens(
     //clear screen
     console.clear_screen();
 
+    let current_map = all_maps
+        .get(&player.position.current_map)
+        .unwrap_or_else(|| {
+            error!(
+                "There is no map for current player position: {}",
+                &player.position.current_map
+            );
+            process::exit(1);
+        });
+    let current_map_explored_tiles = explored_maps.maps.get(&player.position.current_map);
+    let top_status_bar = top_status_bar::draw(player, console);
+
+    if sidebar_state == SidebarState::MapOverview {
+        //the map overview takes up the whole screen below the top status bar
+        let map_overview = map_overview::draw(
+            player,
+            current_map,
+            current_map_explored_tiles,
+            explored_maps
+                .discovered_traps
+                .get(&player.position.current_map),
+            map_exits.get(&player.position.current_map),
+            console.get_width(),
+            console.get_height() - 1,
+        );
+        console.print_screen(0, 0, &top_status_bar);
+        console.print_screen(0, 1, &map_overview);
+        console.draw();
+        return;
+    }
+
     //update the player viewport contents
     let viewport = viewport::draw_viewport_contents(
         player,
-        all_maps
-            .get(&player.position.current_map)
-            .unwrap_or_else(|| {
-                error!(
-                    "There is no map for current player position: {}",
-                    &player.position.current_map
-                );
-                process::exit(1);
-            }),
+        current_map,
+        current_map_explored_tiles,
+        explored_maps
+            .discovered_traps
+            .get(&player.position.current_map),
         entity_position_broadcast,
         viewport_width,
         viewport_height,
     );
 
-    let top_status_bar = top_status_bar::draw(player, console);
     let side_bar = match sidebar_state {
-        SidebarState::StatusBar => side_status_bar::draw(player, console, viewport_width),
+        SidebarState::StatusBar | SidebarState::MapOverview => side_status_bar::draw(
+            player,
+            status_effects,
+            current_map,
+            current_map_explored_tiles,
+            console,
+            viewport_width,
+        ),
         SidebarState::DropItemChoice(item_page_index) => {
             drop_item_choice::draw(player, console, viewport_width, item_page_index)
         }
+        SidebarState::FireTargetChoice(target_index) => fire_target_choice::draw(
+            player,
+            all_maps,
+            entity_position_broadcast,
+            console,
+            viewport_width,
+            target_index,
+        ),
+        SidebarState::AbilityChoice => {
+            ability_choice::draw(player, all_abilities, console, viewport_width)
+        }
+        SidebarState::AbilityTargetChoice(ability_index, target_index) => {
+            ability_target_choice::draw(
+                player,
+                all_abilities,
+                all_maps,
+                entity_position_broadcast,
+                console,
+                viewport_width,
+                ability_index,
+                target_index,
+            )
+        }
         SidebarState::StatUpChoice => stat_up_choice::draw(player, console, viewport_width),
+        SidebarState::BuildBarricadeChoice => {
+            build_barricade_choice::draw(player, console, viewport_width)
+        }
+        SidebarState::CombatStatistics => {
+            combat_statistics::draw(combat_statistics, console, viewport_width)
+        }
     };
     let bottom_text_window =
         bottom_text_window::draw(system_messages, console, viewport_width, viewport_height);
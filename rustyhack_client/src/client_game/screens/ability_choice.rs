@@ -0,0 +1,56 @@
+use console_engine::ConsoleEngine;
+use console_engine::screen::Screen;
+use rustyhack_lib::ecs::ability::{AllAbilities, get_known_abilities};
+use rustyhack_lib::ecs::player::Player;
+
+pub(super) fn draw(
+    player: &Player,
+    all_abilities: &AllAbilities,
+    console: &ConsoleEngine,
+    viewport_width: u32,
+) -> Screen {
+    let mut screen = Screen::new(console.get_width() - viewport_width, console.get_height());
+
+    screen.print(1, 0, "(Esc) to cancel");
+    screen.print(1, 2, "Use which ability?");
+    screen.print(
+        1,
+        4,
+        &("MP: ".to_owned()
+            + &player.stats.current_mana.to_string()
+            + "/"
+            + &player.stats.max_mana.to_string()),
+    );
+    screen.print(
+        1,
+        5,
+        &("SP: ".to_owned()
+            + &player.stats.current_stamina.to_string()
+            + "/"
+            + &player.stats.max_stamina.to_string()),
+    );
+
+    let known_abilities = get_known_abilities(all_abilities, player.stats.level);
+    if known_abilities.is_empty() {
+        screen.print(1, 7, "You don't know any abilities.");
+        return screen;
+    }
+    let mut line_count = 7;
+    for (number, ability) in (1..=9).zip(known_abilities.iter()) {
+        let ability_text = "(".to_owned()
+            + &number.to_string()
+            + ") "
+            + &ability.name
+            + " - "
+            + &ability.cost_string();
+        screen.print(1, line_count, &ability_text);
+        if !ability.description.is_empty() {
+            screen.print(5, line_count + 1, &ability.description);
+            line_count += 1;
+        }
+        line_count += 1;
+    }
+    screen.print(1, line_count + 1, "(F1-F9) use without this menu");
+
+    screen
+}
@@ -0,0 +1,51 @@
+use crate::client_game::input::commands::fire;
+use console_engine::ConsoleEngine;
+use console_engine::screen::Screen;
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::ability::{AllAbilities, get_known_abilities};
+use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::network::packets::EntityPositionBroadcast;
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn draw(
+    player: &Player,
+    all_abilities: &AllAbilities,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+    console: &ConsoleEngine,
+    viewport_width: u32,
+    ability_index: u16,
+    target_index: u16,
+) -> Screen {
+    let mut screen = Screen::new(console.get_width() - viewport_width, console.get_height());
+
+    screen.print(1, 0, "(Esc) to cancel");
+    let known_abilities = get_known_abilities(all_abilities, player.stats.level);
+    let Some(ability) = known_abilities.get(usize::from(ability_index)) else {
+        return screen;
+    };
+    let range = ability.target_range().unwrap_or(0);
+    screen.print(1, 2, &("Use ".to_owned() + &ability.name + " on?"));
+    screen.print(1, 4, &("Range: ".to_owned() + &range.to_string()));
+    screen.print(1, 5, &("Cost: ".to_owned() + &ability.cost_string()));
+
+    let targets = fire::get_targets_in_range(player, all_maps, entity_position_map, range);
+    let selected_index = if targets.is_empty() {
+        0
+    } else {
+        usize::from(target_index) % targets.len()
+    };
+    let mut line_count = 7;
+    for (index, target) in targets.iter().enumerate().take(10) {
+        let marker = if index == selected_index { "> " } else { "  " };
+        let target_text =
+            marker.to_string() + &target.name + " (" + &target.distance.to_string() + " away)";
+        screen.print(1, line_count, &target_text);
+        line_count += 1;
+    }
+
+    screen.print(1, line_count + 1, "(Tab) next target");
+    screen.print(1, line_count + 2, "(Enter) to use");
+
+    screen
+}
@@ -0,0 +1,28 @@
+use console_engine::ConsoleEngine;
+use console_engine::screen::Screen;
+use rustyhack_lib::consts::BARRICADE_GOLD_COST;
+use rustyhack_lib::ecs::player::Player;
+
+pub(super) fn draw(player: &Player, console: &ConsoleEngine, viewport_width: u32) -> Screen {
+    let mut screen = Screen::new(console.get_width() - viewport_width, console.get_height());
+
+    let esc_string = "(Esc) to cancel";
+    let build_string = "Build barricade which way?";
+    let cost_string = "Cost: ".to_owned() + &BARRICADE_GOLD_COST.to_string() + " Gold";
+    let gold_string = "Gold: ".to_owned() + &player.inventory.gold.to_string();
+
+    screen.print(1, 0, esc_string);
+    screen.print(1, 2, build_string);
+    screen.print(1, 4, "(7) North-west");
+    screen.print(1, 5, "(8) North");
+    screen.print(1, 6, "(9) North-east");
+    screen.print(1, 7, "(4) West");
+    screen.print(1, 8, "(6) East");
+    screen.print(1, 9, "(1) South-west");
+    screen.print(1, 10, "(2) South");
+    screen.print(1, 11, "(3) South-east");
+    screen.print(1, 13, &cost_string);
+    screen.print(1, 14, &gold_string);
+
+    screen
+}
@@ -0,0 +1,70 @@
+use crate::client_game::combat_statistics::CombatStatistics;
+use console_engine::ConsoleEngine;
+use console_engine::screen::Screen;
+
+pub(super) fn draw(
+    combat_statistics: &CombatStatistics,
+    console: &ConsoleEngine,
+    viewport_width: u32,
+) -> Screen {
+    let mut screen = Screen::new(console.get_width() - viewport_width, console.get_height());
+
+    let esc_string = "(c) or (Esc) to close";
+    let title_string = "Combat this session:";
+    let session_string = "Session: ".to_owned()
+        + &(combat_statistics.session_start.elapsed().as_secs() / 60).to_string()
+        + "m";
+    let combat_time_string =
+        "In combat: ".to_owned() + &combat_statistics.time_in_combat.as_secs().to_string() + "s";
+
+    let dps_string =
+        "DPS: ".to_owned() + &round_to_one_place(combat_statistics.damage_per_second()).to_string();
+    let hit_rate_string = "Hit rate: ".to_owned()
+        + &combat_statistics.hit_rate_percentage().round().to_string()
+        + "% ("
+        + &combat_statistics.hits.to_string()
+        + "/"
+        + &combat_statistics.attacks_made.to_string()
+        + ")";
+    let critical_hits_string =
+        "Critical hits: ".to_owned() + &combat_statistics.critical_hits.to_string();
+    let damage_dealt_string =
+        "Damage dealt: ".to_owned() + &combat_statistics.damage_dealt.to_string();
+    let highest_hit_string =
+        "Highest hit: ".to_owned() + &combat_statistics.highest_hit.to_string();
+    let kills_string = "Kills: ".to_owned() + &combat_statistics.kills.to_string();
+
+    let damage_taken_string =
+        "Damage taken: ".to_owned() + &combat_statistics.damage_taken.to_string();
+    let block_rate_string = "Blocked: ".to_owned()
+        + &combat_statistics
+            .block_rate_percentage()
+            .round()
+            .to_string()
+        + "% ("
+        + &combat_statistics.attacks_blocked.to_string()
+        + "/"
+        + &combat_statistics.attacks_received.to_string()
+        + ")";
+    let deaths_string = "Deaths: ".to_owned() + &combat_statistics.deaths.to_string();
+
+    screen.print(1, 0, esc_string);
+    screen.print(1, 2, title_string);
+    screen.print(1, 3, &session_string);
+    screen.print(1, 4, &combat_time_string);
+    screen.print(1, 6, &dps_string);
+    screen.print(1, 7, &hit_rate_string);
+    screen.print(1, 8, &critical_hits_string);
+    screen.print(1, 9, &damage_dealt_string);
+    screen.print(1, 10, &highest_hit_string);
+    screen.print(1, 11, &kills_string);
+    screen.print(1, 13, &damage_taken_string);
+    screen.print(1, 14, &block_rate_string);
+    screen.print(1, 15, &deaths_string);
+
+    screen
+}
+
+fn round_to_one_place(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
@@ -0,0 +1,57 @@
+use crate::client_game::input::commands::fire;
+use console_engine::ConsoleEngine;
+use console_engine::screen::Screen;
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::item::{count_carried_items_named, get_ranged_weapon};
+use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::network::packets::EntityPositionBroadcast;
+
+pub(super) fn draw(
+    player: &Player,
+    all_maps: &AllMaps,
+    entity_position_map: &EntityPositionBroadcast,
+    console: &ConsoleEngine,
+    viewport_width: u32,
+    target_index: u16,
+) -> Screen {
+    let mut screen = Screen::new(console.get_width() - viewport_width, console.get_height());
+
+    screen.print(1, 0, "(Esc) to cancel");
+    screen.print(1, 2, "Fire at which target?");
+    if let Some(ranged_weapon) = get_ranged_weapon(&player.inventory) {
+        screen.print(1, 4, &("Weapon: ".to_string() + &ranged_weapon.name));
+        screen.print(
+            1,
+            5,
+            &("Range: ".to_string() + &ranged_weapon.range.to_string()),
+        );
+        if let Some(ammunition) = &ranged_weapon.ammunition {
+            let ammunition_count = count_carried_items_named(&player.inventory.carried, ammunition);
+            screen.print(
+                1,
+                6,
+                &(ammunition.clone() + ": " + &ammunition_count.to_string()),
+            );
+        }
+    }
+
+    let targets = fire::get_ranged_targets(player, all_maps, entity_position_map);
+    let selected_index = if targets.is_empty() {
+        0
+    } else {
+        usize::from(target_index) % targets.len()
+    };
+    let mut line_count = 8;
+    for (index, target) in targets.iter().enumerate().take(10) {
+        let marker = if index == selected_index { "> " } else { "  " };
+        let target_text =
+            marker.to_string() + &target.name + " (" + &target.distance.to_string() + " away)";
+        screen.print(1, line_count, &target_text);
+        line_count += 1;
+    }
+
+    screen.print(1, line_count + 1, "(Tab) next target");
+    screen.print(1, line_count + 2, "(Enter) or (f) to fire");
+
+    screen
+}
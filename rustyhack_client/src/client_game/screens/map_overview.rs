@@ -0,0 +1,196 @@
+use crate::client_consts::{DEFAULT_BG_COLOUR, EXPLORED_NOT_VISIBLE_COLOUR, MAP_EXIT_COLOUR};
+use console_engine::pixel;
+use console_engine::screen::Screen;
+use rustyhack_lib::background_map::BackgroundMap;
+use rustyhack_lib::background_map::tiles::Tile;
+use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::utils::math::i32_from;
+use std::collections::HashSet;
+
+const MAP_TOP_MARGIN: u32 = 2;
+
+pub(super) fn draw(
+    player: &Player,
+    current_map: &BackgroundMap,
+    explored_tiles: Option<&HashSet<(u32, u32)>>,
+    discovered_traps: Option<&HashSet<(u32, u32)>>,
+    current_map_exits: Option<&Vec<(u32, u32, String)>>,
+    width: u32,
+    height: u32,
+) -> Screen {
+    let mut screen = Screen::new(width, height);
+    let no_tiles = HashSet::new();
+    let explored_tiles = explored_tiles.unwrap_or(&no_tiles);
+    let discovered_traps = discovered_traps.unwrap_or(&no_tiles);
+
+    //only exits the player has already found are shown
+    let known_exits: Vec<&(u32, u32, String)> = current_map_exits
+        .map(|map_exits| {
+            map_exits
+                .iter()
+                .filter(|(x, y, _new_map)| explored_tiles.contains(&(*x, *y)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let title_string =
+        "Map of ".to_owned() + &player.position.current_map + " - (w) or (Esc) to close";
+    screen.print(1, 0, &title_string);
+
+    let legend_height = u32::try_from(known_exits.len()).unwrap_or(u32::MAX) + 2;
+    let map_area = MapArea::new(
+        current_map,
+        width.saturating_sub(2),
+        height.saturating_sub(MAP_TOP_MARGIN + legend_height + 1),
+    );
+    draw_map_area(
+        &mut screen,
+        &map_area,
+        current_map,
+        player,
+        explored_tiles,
+        discovered_traps,
+        &known_exits,
+    );
+    draw_legend(
+        &mut screen,
+        player,
+        &known_exits,
+        i32_from(MAP_TOP_MARGIN + map_area.height + 1),
+    );
+    screen
+}
+
+struct MapArea {
+    width: u32,
+    height: u32,
+    scale_x: u32,
+    scale_y: u32,
+}
+
+impl MapArea {
+    fn new(current_map: &BackgroundMap, available_width: u32, available_height: u32) -> MapArea {
+        let map_width = u32::try_from(current_map.data().ncols()).unwrap_or(u32::MAX);
+        let map_height = u32::try_from(current_map.data().nrows()).unwrap_or(u32::MAX);
+        //maps larger than the screen are scaled down, each character then covers several tiles
+        let scale_x = map_width.div_ceil(available_width.max(1)).max(1);
+        let scale_y = map_height.div_ceil(available_height.max(1)).max(1);
+        MapArea {
+            width: map_width.div_ceil(scale_x),
+            height: map_height.div_ceil(scale_y),
+            scale_x,
+            scale_y,
+        }
+    }
+
+    fn contains(&self, screen_x: u32, screen_y: u32, tile_x: u32, tile_y: u32) -> bool {
+        tile_x / self.scale_x == screen_x && tile_y / self.scale_y == screen_y
+    }
+}
+
+fn draw_map_area(
+    screen: &mut Screen,
+    map_area: &MapArea,
+    current_map: &BackgroundMap,
+    player: &Player,
+    explored_tiles: &HashSet<(u32, u32)>,
+    discovered_traps: &HashSet<(u32, u32)>,
+    known_exits: &[&(u32, u32, String)],
+) {
+    for screen_y in 0..map_area.height {
+        for screen_x in 0..map_area.width {
+            let print_x = i32_from(screen_x) + 1;
+            let print_y = i32_from(screen_y + MAP_TOP_MARGIN);
+            if map_area.contains(
+                screen_x,
+                screen_y,
+                player.position.pos_x,
+                player.position.pos_y,
+            ) {
+                screen.set_pxl(
+                    print_x,
+                    print_y,
+                    pixel::pxl_fg(player.display_details.icon, player.display_details.colour),
+                );
+            } else if let Some((exit_x, exit_y, _new_map)) = known_exits
+                .iter()
+                .find(|(x, y, _new_map)| map_area.contains(screen_x, screen_y, *x, *y))
+            {
+                let exit_character = current_map.get_tile_at(*exit_y, *exit_x).character();
+                screen.set_pxl(
+                    print_x,
+                    print_y,
+                    pixel::pxl_fg(exit_character, MAP_EXIT_COLOUR),
+                );
+            } else if let Some(tile) = get_most_visible_explored_tile(
+                map_area,
+                current_map,
+                (screen_x, screen_y),
+                explored_tiles,
+                discovered_traps,
+            ) {
+                screen.set_pxl(
+                    print_x,
+                    print_y,
+                    pixel::pxl_fg(
+                        tile.character(),
+                        tile.colour().unwrap_or(EXPLORED_NOT_VISIBLE_COLOUR),
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn get_most_visible_explored_tile(
+    map_area: &MapArea,
+    current_map: &BackgroundMap,
+    screen_position: (u32, u32),
+    explored_tiles: &HashSet<(u32, u32)>,
+    discovered_traps: &HashSet<(u32, u32)>,
+) -> Option<Tile> {
+    let mut most_visible_tile = None;
+    for tile_y in
+        (screen_position.1 * map_area.scale_y)..((screen_position.1 + 1) * map_area.scale_y)
+    {
+        for tile_x in
+            (screen_position.0 * map_area.scale_x)..((screen_position.0 + 1) * map_area.scale_x)
+        {
+            if !explored_tiles.contains(&(tile_x, tile_y)) {
+                continue;
+            }
+            let tile = current_map.get_known_tile_at(tile_y, tile_x, discovered_traps);
+            //anything is more interesting to show than empty space
+            if tile.character() != ' ' {
+                return Some(tile);
+            }
+            most_visible_tile = Some(tile);
+        }
+    }
+    most_visible_tile
+}
+
+fn draw_legend(
+    screen: &mut Screen,
+    player: &Player,
+    known_exits: &[&(u32, u32, String)],
+    legend_y: i32,
+) {
+    screen.print_fbg(
+        1,
+        legend_y,
+        &(player.display_details.icon.to_string() + " You"),
+        player.display_details.colour,
+        DEFAULT_BG_COLOUR,
+    );
+    for (line_y, (exit_x, exit_y, new_map)) in (legend_y + 1..).zip(known_exits.iter()) {
+        let exit_string = "Exit to ".to_owned()
+            + new_map
+            + " ("
+            + &exit_x.to_string()
+            + ","
+            + &exit_y.to_string()
+            + ")";
+        screen.print_fbg(1, line_y, &exit_string, MAP_EXIT_COLOUR, DEFAULT_BG_COLOUR);
+    }
+}
@@ -2,11 +2,21 @@ use crate::client_consts::DEFAULT_BG_COLOUR;
 use console_engine::ConsoleEngine;
 use console_engine::screen::Screen;
 use crossterm::style::Color;
+use rustyhack_lib::background_map::BackgroundMap;
+use rustyhack_lib::ecs::components::StatusEffects;
 use rustyhack_lib::ecs::item::get_item_name;
 use rustyhack_lib::ecs::player::Player;
 use rustyhack_lib::utils::math::i32_from;
+use std::collections::HashSet;
 
-pub(super) fn draw(player: &Player, console: &ConsoleEngine, viewport_width: u32) -> Screen {
+pub(super) fn draw(
+    player: &Player,
+    status_effects: &StatusEffects,
+    current_map: &BackgroundMap,
+    current_map_explored_tiles: Option<&HashSet<(u32, u32)>>,
+    console: &ConsoleEngine,
+    viewport_width: u32,
+) -> Screen {
     let mut screen = Screen::new(console.get_width() - viewport_width, console.get_height());
 
     let lvl_string = "Lvl: ".to_owned() + &player.stats.level.to_string();
@@ -17,12 +27,30 @@ pub(super) fn draw(player: &Player, console: &ConsoleEngine, viewport_width: u32
         + &player.stats.current_hp.to_string()
         + "/"
         + &player.stats.max_hp.to_string();
+    let mp_string = "MP: ".to_owned()
+        + &player.stats.current_mana.to_string()
+        + "/"
+        + &player.stats.max_mana.to_string();
+    let sp_string = "SP: ".to_owned()
+        + &player.stats.current_stamina.to_string()
+        + "/"
+        + &player.stats.max_stamina.to_string();
     let str_string = "Str: ".to_owned() + &player.stats.str.to_string();
     let dex_string = "Dex: ".to_owned() + &player.stats.dex.to_string();
     let con_string = "Con: ".to_owned() + &player.stats.con.to_string();
+    let attack_speed = player
+        .inventory
+        .equipped
+        .weapon
+        .attack_speed(player.stats.dex);
+    let attack_speed_string =
+        "Atk speed: ".to_owned() + &((attack_speed * 100.0).round() / 100.0).to_string();
     let stat_points_string = "Stat (u)p available!";
 
     let gold_string = "Gold: ".to_owned() + &player.inventory.gold.to_string();
+    let explored_string = "Explored: ".to_owned()
+        + &calculate_explored_percentage(current_map, current_map_explored_tiles).to_string()
+        + "%";
 
     let equipped_title_string = "Equipped:";
     let weapon_string = player.inventory.equipped.weapon.name.clone()
@@ -61,21 +89,33 @@ pub(super) fn draw(player: &Player, console: &ConsoleEngine, viewport_width: u32
     screen.print(1, y + 1, &lvl_string);
     screen.print(1, y + 2, &exp_string);
     screen.print(1, y + 3, &exp_next_string);
+    draw_pvp_status(&mut screen, player, current_map, y + 4);
     screen.print(1, y + 5, &hp_string);
-    screen.print(1, y + 6, &str_string);
-    screen.print(1, y + 7, &dex_string);
-    screen.print(1, y + 8, &con_string);
+    screen.print(1, y + 6, &mp_string);
+    screen.print(1, y + 7, &sp_string);
+    screen.print(1, y + 8, &str_string);
+    screen.print(1, y + 9, &dex_string);
+    screen.print(1, y + 10, &con_string);
+    screen.print(1, y + 11, &attack_speed_string);
     if player.stats.stat_points > 0 {
-        screen.print_fbg(1, y + 9, stat_points_string, Color::Cyan, DEFAULT_BG_COLOUR);
+        screen.print_fbg(
+            1,
+            y + 12,
+            stat_points_string,
+            Color::Cyan,
+            DEFAULT_BG_COLOUR,
+        );
         y += 1;
     }
-    screen.print(1, y + 10, &gold_string);
-    screen.print(1, y + 12, equipped_title_string);
-    screen.print(1, y + 13, &weapon_string);
-    screen.print(1, y + 14, &armour_string);
-    screen.print(1, y + 16, inventory_title_string);
+    y += draw_status_effects(&mut screen, status_effects, y + 12);
+    screen.print(1, y + 13, &gold_string);
+    screen.print(1, y + 14, &explored_string);
+    screen.print(1, y + 16, equipped_title_string);
+    screen.print(1, y + 17, &weapon_string);
+    screen.print(1, y + 18, &armour_string);
+    screen.print(1, y + 20, inventory_title_string);
 
-    for (line_count, item) in (y + 17..).zip(player.inventory.carried.iter()) {
+    for (line_count, item) in (y + 21..).zip(player.inventory.carried.iter()) {
         if line_count > max_y {
             break;
         }
@@ -85,3 +125,47 @@ pub(super) fn draw(player: &Player, console: &ConsoleEngine, viewport_width: u32
 
     screen
 }
+
+fn draw_pvp_status(screen: &mut Screen, player: &Player, current_map: &BackgroundMap, y: i32) {
+    if current_map.is_safe_zone((player.position.pos_x, player.position.pos_y)) {
+        screen.print_fbg(1, y, "Safe zone", Color::Green, DEFAULT_BG_COLOUR);
+    } else if player.stats.pvp_enabled {
+        screen.print_fbg(1, y, "PvP on", Color::Red, DEFAULT_BG_COLOUR);
+    }
+}
+
+//returns how many lines were used
+fn draw_status_effects(screen: &mut Screen, status_effects: &StatusEffects, start_y: i32) -> i32 {
+    for (line_count, active_effect) in (start_y..).zip(status_effects.active.iter()) {
+        let colour = if active_effect.effect.harmful {
+            Color::DarkRed
+        } else {
+            Color::Green
+        };
+        screen.print_fbg(
+            1,
+            line_count,
+            &active_effect.display_text(),
+            colour,
+            DEFAULT_BG_COLOUR,
+        );
+    }
+    i32::try_from(status_effects.active.len()).unwrap_or(0)
+}
+
+fn calculate_explored_percentage(
+    current_map: &BackgroundMap,
+    current_map_explored_tiles: Option<&HashSet<(u32, u32)>>,
+) -> usize {
+    let total_tiles = current_map.count_tiles_within_boundary();
+    if total_tiles == 0 {
+        return 0;
+    }
+    let explored_tiles = current_map_explored_tiles.map_or(0, |explored_tiles| {
+        explored_tiles
+            .iter()
+            .filter(|(x, y)| current_map.is_within_boundary(*y, *x))
+            .count()
+    });
+    (explored_tiles * 100 / total_tiles).min(100)
+}
@@ -1,10 +1,13 @@
-use crate::client_consts::NON_COLLIDABLE_OBJECTS;
+use crate::client_consts::{EXPLORED_NOT_VISIBLE_COLOUR, NON_COLLIDABLE_OBJECTS};
 use console_engine::pixel;
 use console_engine::screen::Screen;
-use rustyhack_lib::background_map::BackgroundMap;
+use rustyhack_lib::background_map::{BackgroundMap, lighting};
+use rustyhack_lib::consts::DEFAULT_PLAYER_VIEW_DISTANCE;
+use rustyhack_lib::ecs::item::get_carried_light_radius;
 use rustyhack_lib::ecs::player::Player;
 use rustyhack_lib::network::packets::EntityPositionBroadcast;
 use rustyhack_lib::utils::math::{i32_from, u32_from, usize_from_i32};
+use std::collections::HashSet;
 
 struct Viewport {
     width: u32,
@@ -22,6 +25,8 @@ struct RelativePosition {
 pub(super) fn draw_viewport_contents(
     player: &Player,
     background_map: &BackgroundMap,
+    explored_tiles: Option<&HashSet<(u32, u32)>>,
+    discovered_traps: Option<&HashSet<(u32, u32)>>,
     entity_position_map: &EntityPositionBroadcast,
     viewport_width: u32,
     viewport_height: u32,
@@ -32,10 +37,37 @@ pub(super) fn draw_viewport_contents(
         viewable_map_top_left_position: RelativePosition { x: 0, y: 0 },
     };
     let mut screen = Screen::new(viewport.width, viewport.height);
+    //tiles currently in view are always shown, even before the server confirms them as explored
+    let visible_tiles: HashSet<(u32, u32)> = lighting::get_lit_visible_tiles(
+        background_map,
+        player.position.pos_x,
+        player.position.pos_y,
+        DEFAULT_PLAYER_VIEW_DISTANCE,
+        get_carried_light_radius(&player.inventory.carried),
+    )
+    .into_iter()
+    .collect();
+    let no_explored_tiles = HashSet::new();
+    let explored_tiles = explored_tiles.unwrap_or(&no_explored_tiles);
+    let no_discovered_traps = HashSet::new();
+    let discovered_traps = discovered_traps.unwrap_or(&no_discovered_traps);
     calculate_viewable_map_coords(&mut viewport, player);
-    draw_viewable_map(&mut screen, background_map, &viewport);
+    draw_viewable_map(
+        &mut screen,
+        background_map,
+        &visible_tiles,
+        explored_tiles,
+        discovered_traps,
+        &viewport,
+    );
     draw_viewport_frame(&mut screen, &viewport);
-    draw_other_entities(&mut screen, player, entity_position_map, &viewport);
+    draw_other_entities(
+        &mut screen,
+        player,
+        entity_position_map,
+        &visible_tiles,
+        &viewport,
+    );
     draw_player(&mut screen, &viewport, player);
     screen
 }
@@ -53,6 +85,7 @@ fn draw_other_entities(
     screen: &mut Screen,
     player: &Player,
     entity_position_map: &EntityPositionBroadcast,
+    visible_tiles: &HashSet<(u32, u32)>,
     viewport: &Viewport,
 ) {
     debug!("Drawing other entities.");
@@ -67,6 +100,7 @@ fn draw_other_entities(
     {
         if *entity_name_or_type != player.player_details.player_name
             && *entity_current_map == player.position.current_map
+            && visible_tiles.contains(&(*entity_position_x, *entity_position_y))
         {
             let relative_entity_position = RelativePosition {
                 x: i32_from(*entity_position_x) - viewport.viewable_map_top_left_position.x,
@@ -108,7 +142,14 @@ fn calculate_viewable_map_coords(viewport: &mut Viewport, player: &Player) {
 }
 
 #[allow(clippy::similar_names)]
-fn draw_viewable_map(screen: &mut Screen, world_map: &BackgroundMap, viewport: &Viewport) {
+fn draw_viewable_map(
+    screen: &mut Screen,
+    world_map: &BackgroundMap,
+    visible_tiles: &HashSet<(u32, u32)>,
+    explored_tiles: &HashSet<(u32, u32)>,
+    discovered_traps: &HashSet<(u32, u32)>,
+    viewport: &Viewport,
+) {
     debug!("Drawing viewable map.");
     let mut viewport_print_y_loc: i32 = 0;
     while viewport_print_y_loc < i32_from(viewport.height) {
@@ -123,17 +164,36 @@ fn draw_viewable_map(screen: &mut Screen, world_map: &BackgroundMap, viewport: &
                 && (usize_from_i32(current_map_print_loc.x)) < world_map.data().ncols()
                 && (usize_from_i32(current_map_print_loc.y) < world_map.data().nrows())
             {
-                screen.print(
-                    viewport_print_x_loc,
-                    viewport_print_y_loc,
-                    &world_map
-                        .get_tile_at(
-                            u32_from(current_map_print_loc.y),
-                            u32_from(current_map_print_loc.x),
-                        )
-                        .character()
-                        .to_string(),
+                let map_tile = (
+                    u32_from(current_map_print_loc.x),
+                    u32_from(current_map_print_loc.y),
                 );
+                let tile = world_map.get_known_tile_at(map_tile.1, map_tile.0, discovered_traps);
+                let tile_character = tile.character();
+                if visible_tiles.contains(&map_tile) {
+                    if let Some(tile_colour) = tile.colour() {
+                        screen.set_pxl(
+                            viewport_print_x_loc,
+                            viewport_print_y_loc,
+                            pixel::pxl_fg(tile_character, tile_colour),
+                        );
+                    } else {
+                        screen.print(
+                            viewport_print_x_loc,
+                            viewport_print_y_loc,
+                            &tile_character.to_string(),
+                        );
+                    }
+                } else if explored_tiles.contains(&map_tile) {
+                    screen.set_pxl(
+                        viewport_print_x_loc,
+                        viewport_print_y_loc,
+                        pixel::pxl_fg(tile_character, EXPLORED_NOT_VISIBLE_COLOUR),
+                    );
+                } else {
+                    //not yet discovered, draw nothing
+                    screen.print(viewport_print_x_loc, viewport_print_y_loc, " ");
+                }
             } else {
                 screen.print(viewport_print_x_loc, viewport_print_y_loc, " ");
             }
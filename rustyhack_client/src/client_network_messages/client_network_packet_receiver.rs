@@ -39,48 +39,7 @@ fn run(receiver: &Receiver<SocketEvent>, incoming_server_messages: &Sender<Serve
                     };
                     debug!("Received {player_reply:?} from {address:?}");
 
-                    let channel_send_status =
-                        match player_reply.0 {
-                            ServerMessage::PlayerJoined(player) => {
-                                incoming_server_messages.send(ServerMessage::PlayerJoined(player))
-                            }
-                            ServerMessage::AllMaps(all_maps) => {
-                                incoming_server_messages.send(ServerMessage::AllMaps(all_maps))
-                            }
-                            ServerMessage::AllMapsChunk(all_maps_chunk) => incoming_server_messages
-                                .send(ServerMessage::AllMapsChunk(all_maps_chunk)),
-                            ServerMessage::AllMapsChunksComplete => {
-                                incoming_server_messages.send(ServerMessage::AllMapsChunksComplete)
-                            }
-                            ServerMessage::UpdatePosition(position) => incoming_server_messages
-                                .send(ServerMessage::UpdatePosition(position)),
-                            ServerMessage::UpdateOtherEntities(entity_position_broadcast) => {
-                                incoming_server_messages.send(ServerMessage::UpdateOtherEntities(
-                                    entity_position_broadcast,
-                                ))
-                            }
-                            ServerMessage::PlayerAlreadyOnline => {
-                                incoming_server_messages.send(ServerMessage::PlayerAlreadyOnline)
-                            }
-                            ServerMessage::UpdateStats(stats) => {
-                                incoming_server_messages.send(ServerMessage::UpdateStats(stats))
-                            }
-                            ServerMessage::UpdateInventory(inventory) => incoming_server_messages
-                                .send(ServerMessage::UpdateInventory(inventory)),
-                            ServerMessage::SystemMessage(message) => {
-                                incoming_server_messages.send(ServerMessage::SystemMessage(message))
-                            }
-                        };
-
-                    #[allow(clippy::ignored_unit_patterns)]
-                    match channel_send_status {
-                        Ok(_) => {
-                            //do nothing
-                        }
-                        Err(message) => {
-                            warn!("Failed to send message via thread channel: {}", &message);
-                        }
-                    }
+                    forward_server_message(player_reply.0, incoming_server_messages);
                 }
                 SocketEvent::Connect(connect_event) => {
                     info!("Server connected at: {connect_event}");
@@ -99,3 +58,73 @@ fn run(receiver: &Receiver<SocketEvent>, incoming_server_messages: &Sender<Serve
         }
     }
 }
+
+fn forward_server_message(
+    server_message: ServerMessage,
+    incoming_server_messages: &Sender<ServerMessage>,
+) {
+    let channel_send_status = match server_message {
+        ServerMessage::PlayerJoined(player) => {
+            incoming_server_messages.send(ServerMessage::PlayerJoined(player))
+        }
+        ServerMessage::AllMaps(all_maps) => {
+            incoming_server_messages.send(ServerMessage::AllMaps(all_maps))
+        }
+        ServerMessage::AllMapsChunk(all_maps_chunk) => {
+            incoming_server_messages.send(ServerMessage::AllMapsChunk(all_maps_chunk))
+        }
+        ServerMessage::AllMapsChunksComplete => {
+            incoming_server_messages.send(ServerMessage::AllMapsChunksComplete)
+        }
+        ServerMessage::UpdatePosition(position) => {
+            incoming_server_messages.send(ServerMessage::UpdatePosition(position))
+        }
+        ServerMessage::UpdateOtherEntities(entity_position_broadcast) => incoming_server_messages
+            .send(ServerMessage::UpdateOtherEntities(
+                entity_position_broadcast,
+            )),
+        ServerMessage::PlayerAlreadyOnline => {
+            incoming_server_messages.send(ServerMessage::PlayerAlreadyOnline)
+        }
+        ServerMessage::UpdateStats(stats) => {
+            incoming_server_messages.send(ServerMessage::UpdateStats(stats))
+        }
+        ServerMessage::UpdateInventory(inventory) => {
+            incoming_server_messages.send(ServerMessage::UpdateInventory(inventory))
+        }
+        ServerMessage::UpdateStatusEffects(status_effects) => {
+            incoming_server_messages.send(ServerMessage::UpdateStatusEffects(status_effects))
+        }
+        ServerMessage::SystemMessage(message) => {
+            incoming_server_messages.send(ServerMessage::SystemMessage(message))
+        }
+        ServerMessage::UpdateExploredTiles(explored_tiles) => {
+            incoming_server_messages.send(ServerMessage::UpdateExploredTiles(explored_tiles))
+        }
+        ServerMessage::UpdateDiscoveredTraps(discovered_traps) => {
+            incoming_server_messages.send(ServerMessage::UpdateDiscoveredTraps(discovered_traps))
+        }
+        ServerMessage::UpdateTiles(tiles) => {
+            incoming_server_messages.send(ServerMessage::UpdateTiles(tiles))
+        }
+        ServerMessage::UpdateMapExits(map_exits) => {
+            incoming_server_messages.send(ServerMessage::UpdateMapExits(map_exits))
+        }
+        ServerMessage::AbilityDefinitions(abilities) => {
+            incoming_server_messages.send(ServerMessage::AbilityDefinitions(abilities))
+        }
+        ServerMessage::CombatEvent(combat_event) => {
+            incoming_server_messages.send(ServerMessage::CombatEvent(combat_event))
+        }
+    };
+
+    #[allow(clippy::ignored_unit_patterns)]
+    match channel_send_status {
+        Ok(_) => {
+            //do nothing
+        }
+        Err(message) => {
+            warn!("Failed to send message via thread channel: {}", &message);
+        }
+    }
+}
@@ -41,7 +41,7 @@ fn wait_for_new_player_response(channel_receiver: &Receiver<ServerMessage>) -> P
                 ServerMessage::PlayerJoined(message) => {
                     info!("New player creation confirmed.");
                     new_player_confirmed = true;
-                    player = message;
+                    player = *message;
                 }
                 ServerMessage::PlayerAlreadyOnline => {
                     error!(
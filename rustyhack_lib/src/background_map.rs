@@ -1,15 +1,21 @@
 pub mod character_map;
+pub mod field_of_view;
+pub mod lighting;
 pub mod tiles;
 
 use crate::background_map::tiles::Tile;
 use bincode::{Decode, Encode};
 use ndarray::Array2;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Clone, Debug, Encode, Decode)]
 pub struct BackgroundMap {
     #[bincode(with_serde)]
     pub data: Array2<Tile>,
+    pub ambient_light: u32,
+    //nobody can fight on a safe zone map, or on the safe zone tiles of any other map
+    pub safe_zone: bool,
+    pub safe_zone_tiles: HashSet<(u32, u32)>,
 }
 
 impl BackgroundMap {
@@ -24,6 +30,62 @@ impl BackgroundMap {
             .get((y as usize, x as usize))
             .unwrap_or(&Tile::EmptySpace)
     }
+
+    //tile as known to a player, hidden traps are left out of the maps sent to clients
+    #[must_use]
+    pub fn get_known_tile_at(
+        &self,
+        y: u32,
+        x: u32,
+        discovered_traps: &HashSet<(u32, u32)>,
+    ) -> Tile {
+        if discovered_traps.contains(&(x, y)) {
+            Tile::Trap
+        } else {
+            *self.get_tile_at(y, x)
+        }
+    }
+
+    pub fn hide_traps(&mut self) {
+        for tile in &mut self.data {
+            if *tile == Tile::Trap {
+                *tile = Tile::EmptySpace;
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn is_safe_zone(&self, tile: (u32, u32)) -> bool {
+        self.safe_zone || self.safe_zone_tiles.contains(&tile)
+    }
+
+    #[must_use]
+    pub fn is_within_boundary(&self, y: u32, x: u32) -> bool {
+        match self.get_boundary_range(y as usize) {
+            Some((first, last)) => (first..=last).contains(&(x as usize)),
+            None => false,
+        }
+    }
+
+    #[must_use]
+    pub fn count_tiles_within_boundary(&self) -> usize {
+        (0..self.data.nrows())
+            .filter_map(|y| self.get_boundary_range(y))
+            .map(|(first, last)| last - first + 1)
+            .sum()
+    }
+
+    fn get_boundary_range(&self, y: usize) -> Option<(usize, usize)> {
+        //maps are enclosed by a boundary in any shape, so treat everything between
+        //the first and last boundary tile on each row as part of the map
+        if y >= self.data.nrows() {
+            return None;
+        }
+        let row = self.data.row(y);
+        let first = row.iter().position(|tile| *tile == Tile::Boundary)?;
+        let last = row.iter().rposition(|tile| *tile == Tile::Boundary)?;
+        Some((first, last))
+    }
 }
 
 pub type AllMaps = HashMap<String, BackgroundMap>;
@@ -1,4 +1,5 @@
 use crate::background_map::tiles::door::Door;
+use crate::background_map::tiles::teleport_pad::TeleportPad;
 use crate::background_map::tiles::wall::Wall;
 use crate::background_map::tiles::{OpenState, Tile};
 
@@ -11,9 +12,15 @@ pub fn map_character_to_tile(x: u32, y: u32, character: char) -> Tile {
         '#' => Tile::Boundary,
         '<' => Tile::UpLadder,
         '>' => Tile::DownLadder,
+        '!' => Tile::Torch,
+        '~' => Tile::Water,
+        '=' => Tile::Lava,
+        '"' => Tile::Acid,
+        '^' => Tile::Trap,
+        'O' => Tile::TeleportPad(TeleportPad::new(x, y)),
         '+' => Tile::Door(Door::new(x, y, OpenState::Closed)),
         '/' => Tile::Door(Door::new(x, y, OpenState::Open)),
-        '|' | '*' | '-' | ',' => Tile::Wall(Wall::new(x, y, character)),
+        '|' | '*' | '-' | ',' | '&' => Tile::Wall(Wall::new(x, y, character)),
         _ => Tile::EmptySpace,
     }
 }
@@ -0,0 +1,70 @@
+use crate::background_map::BackgroundMap;
+use crate::utils::math::{i32_from, u32_from};
+
+#[must_use]
+pub fn get_visible_tiles(
+    background_map: &BackgroundMap,
+    origin_x: u32,
+    origin_y: u32,
+    view_distance: u32,
+) -> Vec<(u32, u32)> {
+    let mut visible_tiles = vec![];
+    let distance = i32_from(view_distance);
+    for diff_y in -distance..=distance {
+        for diff_x in -distance..=distance {
+            //only look in a rough circle rather than a square
+            if diff_x * diff_x + diff_y * diff_y > distance * distance {
+                continue;
+            }
+            let target_x = i32_from(origin_x) + diff_x;
+            let target_y = i32_from(origin_y) + diff_y;
+            if target_x < 0
+                || target_y < 0
+                || target_x >= i32::try_from(background_map.data().ncols()).unwrap_or(i32::MAX)
+                || target_y >= i32::try_from(background_map.data().nrows()).unwrap_or(i32::MAX)
+            {
+                continue;
+            }
+            if has_line_of_sight(
+                background_map,
+                (origin_x, origin_y),
+                (u32_from(target_x), u32_from(target_y)),
+            ) {
+                visible_tiles.push((u32_from(target_x), u32_from(target_y)));
+            }
+        }
+    }
+    visible_tiles
+}
+
+#[must_use]
+pub fn has_line_of_sight(background_map: &BackgroundMap, from: (u32, u32), to: (u32, u32)) -> bool {
+    //bresenham's line, the target tile itself can always be seen if nothing is in the way
+    let (mut x, mut y) = (i32_from(from.0), i32_from(from.1));
+    let (target_x, target_y) = (i32_from(to.0), i32_from(to.1));
+    let diff_x = (target_x - x).abs();
+    let diff_y = -(target_y - y).abs();
+    let step_x = if x < target_x { 1 } else { -1 };
+    let step_y = if y < target_y { 1 } else { -1 };
+    let mut error = diff_x + diff_y;
+
+    while (x, y) != (target_x, target_y) {
+        if (x, y) != (i32_from(from.0), i32_from(from.1))
+            && background_map
+                .get_tile_at(u32_from(y), u32_from(x))
+                .blocks_sight()
+        {
+            return false;
+        }
+        let doubled_error = 2 * error;
+        if doubled_error >= diff_y {
+            error += diff_y;
+            x += step_x;
+        }
+        if doubled_error <= diff_x {
+            error += diff_x;
+            y += step_y;
+        }
+    }
+    true
+}
@@ -0,0 +1,87 @@
+use crate::background_map::BackgroundMap;
+use crate::background_map::field_of_view;
+use crate::background_map::tiles::Tile;
+use crate::consts::TORCH_TILE_LIGHT_RADIUS;
+use crate::utils::math::i32_from;
+
+#[must_use]
+pub fn get_lit_visible_tiles(
+    background_map: &BackgroundMap,
+    origin_x: u32,
+    origin_y: u32,
+    view_distance: u32,
+    light_radius: u32,
+) -> Vec<(u32, u32)> {
+    let visible_tiles =
+        field_of_view::get_visible_tiles(background_map, origin_x, origin_y, view_distance);
+    if background_map.ambient_light >= view_distance {
+        //map is bright enough that everything in view is lit
+        return visible_tiles;
+    }
+    let light_sources = get_light_sources_near(
+        background_map,
+        (origin_x, origin_y),
+        view_distance + TORCH_TILE_LIGHT_RADIUS,
+    );
+    visible_tiles
+        .into_iter()
+        .filter(|tile| {
+            is_tile_lit(
+                background_map,
+                *tile,
+                (origin_x, origin_y),
+                light_radius,
+                &light_sources,
+            )
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn is_tile_lit(
+    background_map: &BackgroundMap,
+    tile: (u32, u32),
+    viewer: (u32, u32),
+    viewer_light_radius: u32,
+    light_sources: &[(u32, u32)],
+) -> bool {
+    //tiles near the viewer are lit by ambient light or whatever the viewer is carrying
+    if is_within_distance(
+        viewer,
+        tile,
+        background_map.ambient_light.max(viewer_light_radius),
+    ) {
+        return true;
+    }
+    light_sources.iter().any(|light_source| {
+        is_within_distance(*light_source, tile, TORCH_TILE_LIGHT_RADIUS)
+            && field_of_view::has_line_of_sight(background_map, *light_source, tile)
+    })
+}
+
+#[must_use]
+pub fn get_light_sources_near(
+    background_map: &BackgroundMap,
+    origin: (u32, u32),
+    distance: u32,
+) -> Vec<(u32, u32)> {
+    let mut light_sources = vec![];
+    for ((y, x), tile) in background_map.data().indexed_iter() {
+        if *tile == Tile::Torch {
+            let light_source = (
+                u32::try_from(x).unwrap_or(u32::MAX),
+                u32::try_from(y).unwrap_or(u32::MAX),
+            );
+            if is_within_distance(origin, light_source, distance) {
+                light_sources.push(light_source);
+            }
+        }
+    }
+    light_sources
+}
+
+fn is_within_distance(from: (u32, u32), to: (u32, u32), distance: u32) -> bool {
+    let diff_x = i32_from(from.0) - i32_from(to.0);
+    let diff_y = i32_from(from.1) - i32_from(to.1);
+    diff_x * diff_x + diff_y * diff_y <= i32_from(distance) * i32_from(distance)
+}
@@ -1,9 +1,12 @@
 pub mod door;
+pub mod teleport_pad;
 pub mod wall;
 
 use crate::background_map::tiles::door::Door;
+use crate::background_map::tiles::teleport_pad::TeleportPad;
 use crate::background_map::tiles::wall::Wall;
 use bincode::{Decode, Encode};
+use crossterm::style::Color;
 use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 
@@ -31,6 +34,13 @@ pub enum Tile {
     Door(Door),
     UpLadder,
     DownLadder,
+    Torch,
+    Water,
+    Lava,
+    Acid,
+    Trap,
+    TeleportPad(TeleportPad),
+    BindStone,
     EmptySpace,
     Boundary,
     NewLine,
@@ -46,8 +56,38 @@ impl Tile {
             Tile::Door(door) => door.character_icon,
             Tile::UpLadder => '<',
             Tile::DownLadder => '>',
+            Tile::Torch => '!',
+            Tile::Water => '~',
+            Tile::Lava => '=',
+            Tile::Acid => '"',
+            Tile::Trap => '^',
+            Tile::TeleportPad(teleport_pad) => teleport_pad.character_icon,
+            Tile::BindStone => '_',
             Tile::Boundary => '#',
             Tile::EmptySpace | Tile::NewLine | Tile::CarriageReturn | Tile::EndOfFile => ' ',
         }
     }
+
+    #[must_use]
+    pub fn colour(&self) -> Option<Color> {
+        match self {
+            Tile::Torch => Some(Color::Yellow),
+            Tile::Water => Some(Color::Blue),
+            Tile::Lava => Some(Color::Red),
+            Tile::Acid => Some(Color::Green),
+            Tile::Trap => Some(Color::DarkRed),
+            Tile::TeleportPad(_) => Some(Color::Magenta),
+            Tile::BindStone => Some(Color::Cyan),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn blocks_sight(&self) -> bool {
+        match self {
+            Tile::Wall(_) | Tile::Boundary | Tile::Torch => true,
+            Tile::Door(door) => door.open_state == OpenState::Closed,
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,21 @@
+use crate::background_map::tiles::TilePosition;
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, Encode, Decode)]
+pub struct TeleportPad {
+    pub tile_position: TilePosition,
+    pub character_icon: char,
+    pub destination: Option<TilePosition>,
+}
+
+impl TeleportPad {
+    #[must_use]
+    pub fn new(x: u32, y: u32) -> TeleportPad {
+        TeleportPad {
+            tile_position: TilePosition { x, y },
+            character_icon: 'O',
+            destination: None,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::background_map::tiles::{Collidable, TilePosition};
+use crate::consts::BARRICADE_TILE_CHARACTER;
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
@@ -18,4 +19,10 @@ impl Wall {
             collidable: Collidable::True,
         }
     }
+
+    #[must_use]
+    pub fn is_breakable(&self) -> bool {
+        //rubble and player built barricades can be broken down, other walls are permanent
+        self.character_icon == '*' || self.character_icon == BARRICADE_TILE_CHARACTER
+    }
 }
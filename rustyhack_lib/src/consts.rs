@@ -5,12 +5,27 @@ pub const DEFAULT_PLAYER_ICON: char = '@';
 pub const DEFAULT_PLAYER_COLOUR: Color = Color::Magenta;
 pub const DEFAULT_PLAYER_POSITION_X: u32 = 16;
 pub const DEFAULT_PLAYER_POSITION_Y: u32 = 14;
+pub const DEFAULT_PLAYER_VIEW_DISTANCE: u32 = 8;
+pub const TORCH_TILE_LIGHT_RADIUS: u32 = 5;
+pub const BARRICADE_TILE_CHARACTER: char = '&';
+pub const BARRICADE_GOLD_COST: u32 = 10;
 pub const DEAD_MAP: &str = "Dead";
 pub const DEAD_ICON: char = ' ';
 pub const DEFAULT_MONSTER_TYPE: &str = "default_monster";
 pub const DEFAULT_MONSTER_ICON: char = 'x';
 pub const DEFAULT_ITEM_ICON: char = ',';
 pub const DEFAULT_ITEM_COLOUR: Color = Color::DarkYellow;
+pub const CORPSE_ICON: char = '%';
+pub const CORPSE_COLOUR: Color = Color::Grey;
 pub const DEFAULT_MONSTER_COLOUR: Color = Color::Red;
 pub const DEFAULT_MONSTER_POSITION_X: u32 = 20;
 pub const DEFAULT_MONSTER_POSITION_Y: u32 = 20;
+pub const DEFAULT_MONSTER_AGGRO_RADIUS: u32 = 10;
+pub const DEFAULT_MONSTER_LEASH_RANGE: u32 = 10;
+pub const DEFAULT_MONSTER_FLEE_HP_PERCENTAGE: u8 = 25;
+pub const DEFAULT_MONSTER_PREFERRED_DISTANCE: u32 = 4;
+pub const DEFAULT_WEAPON_SPEED: f32 = 1.0;
+pub const PLAYER_FACTION: &str = "Players";
+pub const DEFAULT_MONSTER_FACTION: &str = "Monsters";
+pub const DEFAULT_BOSS_ABILITY_INTERVAL_TICKS: u32 = 3;
+pub const DEFAULT_ABILITY_MIN_LEVEL: u32 = 1;
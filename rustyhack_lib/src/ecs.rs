@@ -1,5 +1,7 @@
+pub mod ability;
 pub mod components;
 pub mod inventory;
 pub mod item;
 pub mod monster;
 pub mod player;
+pub mod status_effect;
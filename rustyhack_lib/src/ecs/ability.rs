@@ -0,0 +1,89 @@
+use crate::consts::DEFAULT_ABILITY_MIN_LEVEL;
+use crate::ecs::inventory::DamageType;
+use crate::ecs::status_effect::{OnHitStatusEffect, StatusEffect};
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+pub type AllAbilities = Vec<Ability>;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct Ability {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub cost: AbilityCost,
+    //server ticks before the ability can be used again
+    #[serde(default)]
+    pub cooldown_ticks: u32,
+    #[serde(default = "default_min_level")]
+    pub min_level: u32,
+    pub effect: AbilityEffect,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub enum AbilityCost {
+    Mana(f32),
+    Stamina(f32),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub enum AbilityEffect {
+    Damage {
+        damage_range: Range<f32>,
+        accuracy: f32,
+        range: u32,
+        #[serde(default)]
+        on_hit: Option<OnHitStatusEffect>,
+        #[serde(default)]
+        damage_type: DamageType,
+    },
+    AreaDamage {
+        damage_range: Range<f32>,
+        accuracy: f32,
+        radius: u32,
+        #[serde(default)]
+        on_hit: Option<OnHitStatusEffect>,
+        #[serde(default)]
+        damage_type: DamageType,
+    },
+    Heal {
+        amount: f32,
+    },
+    //a status effect on the user, like a stat boost or healing over time
+    Buff {
+        effect: StatusEffect,
+    },
+}
+
+impl Ability {
+    //only single target damage needs the player to pick a target first
+    #[must_use]
+    pub fn target_range(&self) -> Option<u32> {
+        match self.effect {
+            AbilityEffect::Damage { range, .. } => Some(range),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn cost_string(&self) -> String {
+        match self.cost {
+            AbilityCost::Mana(amount) => amount.to_string() + " MP",
+            AbilityCost::Stamina(amount) => amount.to_string() + " SP",
+        }
+    }
+}
+
+//abilities are shown and used in order of the level they are learnt at
+#[must_use]
+pub fn get_known_abilities(all_abilities: &AllAbilities, level: u32) -> Vec<&Ability> {
+    all_abilities
+        .iter()
+        .filter(|ability| ability.min_level <= level)
+        .collect()
+}
+
+fn default_min_level() -> u32 {
+    DEFAULT_ABILITY_MIN_LEVEL
+}
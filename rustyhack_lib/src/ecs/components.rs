@@ -4,13 +4,16 @@ use crate::consts::{
 };
 use crate::ecs::inventory::Equipment;
 use crate::ecs::item::Item;
-use crate::ecs::monster::Monster;
+use crate::ecs::monster::{BossDetails, EliteAffix, Faction, Monster, MonsterBehaviour};
 use crate::ecs::player::Player;
+use crate::ecs::status_effect::ActiveStatusEffect;
 use bincode::{Decode, Encode};
 use crossterm::style::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, PartialEq, Encode, Decode)]
 pub enum EntityType {
     Monster(Monster),
@@ -96,6 +99,9 @@ pub struct PlayerDetails {
     pub player_name: String,
     pub client_addr: String,
     pub currently_online: bool,
+    //set by stepping onto a bind stone, otherwise players respawn at the default position
+    #[serde(default)]
+    pub respawn_position: Option<Position>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
@@ -104,8 +110,26 @@ pub struct MonsterDetails {
     pub id: Uuid,
     pub monster_type: String,
     pub spawn_position: Position,
+    //index of the entry in the map spawns this monster counts towards
+    #[serde(default)]
+    pub spawn_index: Option<usize>,
     #[bincode(with_serde)]
     pub current_target: Option<Uuid>,
+    #[serde(default)]
+    pub behaviour: MonsterBehaviour,
+    //index into the behaviour patrol waypoints
+    #[serde(default)]
+    pub next_waypoint: usize,
+    #[serde(default)]
+    pub boss: Option<BossDetails>,
+    //the boss that summoned this monster, summoned monsters don't respawn or drop loot
+    #[bincode(with_serde)]
+    #[serde(default)]
+    pub summoned_by: Option<Uuid>,
+    #[serde(default)]
+    pub elite_affixes: Vec<EliteAffix>,
+    #[serde(default)]
+    pub faction: Faction,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
@@ -120,6 +144,14 @@ pub struct Stats {
     pub update_available: bool,
     pub current_hp: f32,
     pub max_hp: f32,
+    #[serde(default)]
+    pub current_mana: f32,
+    #[serde(default)]
+    pub max_mana: f32,
+    #[serde(default)]
+    pub current_stamina: f32,
+    #[serde(default)]
+    pub max_stamina: f32,
     pub str: f32,
     pub dex: f32,
     pub con: f32,
@@ -128,6 +160,12 @@ pub struct Stats {
     pub exp: u32,
     pub exp_next: u32,
     pub in_combat: bool,
+    //players can only fight each other when both have opted in
+    #[serde(default)]
+    pub pvp_enabled: bool,
+    //server ticks left before the pvp flag can be changed again
+    #[serde(default)]
+    pub pvp_toggle_cooldown: u32,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode, Default)]
@@ -137,3 +175,39 @@ pub struct Inventory {
     pub equipped: Equipment,
     pub carried: Vec<Item>,
 }
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode, Default)]
+pub struct AbilityTimers {
+    //server ticks left before each ability can be used again
+    pub cooldowns: HashMap<String, u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode, Default)]
+pub struct StatusEffects {
+    pub update_available: bool,
+    //stat modifiers are already added to stats, and taken away when the effect wears off
+    pub active: Vec<ActiveStatusEffect>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode, Default)]
+pub struct ExploredMaps {
+    pub maps: HashMap<String, HashSet<(u32, u32)>>,
+    #[serde(default)]
+    pub discovered_traps: HashMap<String, HashSet<(u32, u32)>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct BossState {
+    pub current_phase: usize,
+    pub ticks_since_ability: u32,
+    //summoned monsters that are still alive
+    pub summons: Vec<Uuid>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TileChange {
+    pub map: String,
+    pub x: u32,
+    pub y: u32,
+    pub character: char,
+}
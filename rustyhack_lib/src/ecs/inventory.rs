@@ -1,5 +1,8 @@
+use crate::consts::DEFAULT_WEAPON_SPEED;
+use crate::ecs::status_effect::OnHitStatusEffect;
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::ops::Range;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode, Default)]
@@ -13,6 +16,18 @@ pub struct Weapon {
     pub name: String,
     pub damage_range: Range<f32>,
     pub accuracy: f32,
+    #[serde(default = "default_weapon_speed")]
+    pub speed: f32,
+    //how far away the weapon can hit from, anything with no range is melee only
+    #[serde(default)]
+    pub range: u32,
+    //name of the carried item used up by each ranged attack
+    #[serde(default)]
+    pub ammunition: Option<String>,
+    #[serde(default)]
+    pub on_hit: Option<OnHitStatusEffect>,
+    #[serde(default)]
+    pub damage_type: DamageType,
 }
 
 impl Default for Weapon {
@@ -21,14 +36,34 @@ impl Default for Weapon {
             name: "Wooden Sword".to_string(),
             damage_range: 5.0..10.0,
             accuracy: 75.0,
+            speed: DEFAULT_WEAPON_SPEED,
+            range: 0,
+            ammunition: None,
+            on_hit: None,
+            damage_type: DamageType::default(),
         }
     }
 }
 
+impl Weapon {
+    //relative to a speed 1.0 weapon with no dex, matches how often the server lets the wielder attack
+    #[must_use]
+    pub fn attack_speed(&self, dex: f32) -> f32 {
+        self.speed * ((dex / 100.0) + 1.0)
+    }
+}
+
+fn default_weapon_speed() -> f32 {
+    DEFAULT_WEAPON_SPEED
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct Armour {
     pub name: String,
     pub damage_reduction_percentage: f32,
+    //extra damage reduction against particular damage types, negative for weaknesses
+    #[serde(default)]
+    pub resistances: HashMap<DamageType, f32>,
 }
 
 impl Default for Armour {
@@ -36,11 +71,42 @@ impl Default for Armour {
         Armour {
             name: "Cloth Shirt".to_string(),
             damage_reduction_percentage: 5.0,
+            resistances: HashMap::new(),
         }
     }
 }
 
+impl Armour {
+    #[must_use]
+    pub fn get_resistance(&self, damage_type: DamageType) -> f32 {
+        self.resistances.get(&damage_type).copied().unwrap_or(0.0)
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize, Encode, Decode,
+)]
+pub enum DamageType {
+    #[default]
+    Slashing,
+    Piercing,
+    Blunt,
+    Fire,
+    Poison,
+}
+
+//shields only need to be carried, the best one carried is used for blocking
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct Shield {
+    pub name: String,
+    pub block_chance_percentage: f32,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct Trinket {
     pub name: String,
+    #[serde(default)]
+    pub light_radius: u32,
+    #[serde(default)]
+    pub digging_power: u16,
 }
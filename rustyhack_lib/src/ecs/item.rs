@@ -1,4 +1,5 @@
-use crate::ecs::inventory::{Armour, Trinket, Weapon};
+use crate::ecs::components::Inventory;
+use crate::ecs::inventory::{Armour, Shield, Trinket, Weapon};
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,26 @@ pub enum Item {
     Armour(Armour),
     Gold(u32),
     Trinket(Trinket),
+    Shield(Shield),
+    Corpse(Corpse),
+}
+
+//container left behind when a player dies, only the owner can loot it until protection runs out
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct Corpse {
+    pub owner_name: String,
+    pub items: Vec<Item>,
+    pub protected_ticks: u32,
+    //server ticks since the corpse was dropped, it rots away after a while
+    #[serde(default)]
+    pub age_ticks: u32,
+}
+
+impl Corpse {
+    #[must_use]
+    pub fn can_be_looted_by(&self, player_name: &str) -> bool {
+        self.owner_name == player_name || self.protected_ticks == 0
+    }
 }
 
 #[must_use]
@@ -17,5 +38,72 @@ pub fn get_item_name(item: &Item) -> String {
         Item::Armour(armour) => armour.name.clone(),
         Item::Gold(amount) => amount.to_string() + " Gold",
         Item::Trinket(trinket) => trinket.name.clone(),
+        Item::Shield(shield) => shield.name.clone(),
+        Item::Corpse(corpse) => "Corpse of ".to_string() + &corpse.owner_name,
+    }
+}
+
+#[must_use]
+pub fn get_item_light_radius(item: &Item) -> u32 {
+    match item {
+        Item::Trinket(trinket) => trinket.light_radius,
+        _ => 0,
+    }
+}
+
+#[must_use]
+pub fn get_item_digging_power(item: &Item) -> u16 {
+    match item {
+        Item::Trinket(trinket) => trinket.digging_power,
+        _ => 0,
     }
 }
+
+#[must_use]
+pub fn get_carried_digging_power(carried: &[Item]) -> u16 {
+    carried
+        .iter()
+        .map(get_item_digging_power)
+        .max()
+        .unwrap_or(0)
+}
+
+#[must_use]
+pub fn get_carried_light_radius(carried: &[Item]) -> u32 {
+    carried.iter().map(get_item_light_radius).max().unwrap_or(0)
+}
+
+#[must_use]
+pub fn get_carried_block_chance(carried: &[Item]) -> f32 {
+    carried
+        .iter()
+        .filter_map(|item| match item {
+            Item::Shield(shield) => Some(shield.block_chance_percentage),
+            _ => None,
+        })
+        .fold(0.0, f32::max)
+}
+
+//the equipped weapon if it can fire, otherwise the longest ranged weapon being carried
+#[must_use]
+pub fn get_ranged_weapon(inventory: &Inventory) -> Option<&Weapon> {
+    if inventory.equipped.weapon.range > 0 {
+        return Some(&inventory.equipped.weapon);
+    }
+    inventory
+        .carried
+        .iter()
+        .filter_map(|item| match item {
+            Item::Weapon(weapon) if weapon.range > 0 => Some(weapon),
+            _ => None,
+        })
+        .max_by_key(|weapon| weapon.range)
+}
+
+#[must_use]
+pub fn count_carried_items_named(carried: &[Item], name: &str) -> usize {
+    carried
+        .iter()
+        .filter(|item| get_item_name(item) == name)
+        .count()
+}
@@ -1,9 +1,13 @@
 use crate::consts::{
-    DEFAULT_MAP, DEFAULT_MONSTER_COLOUR, DEFAULT_MONSTER_ICON, DEFAULT_MONSTER_POSITION_X,
-    DEFAULT_MONSTER_POSITION_Y, DEFAULT_MONSTER_TYPE,
+    DEFAULT_BOSS_ABILITY_INTERVAL_TICKS, DEFAULT_MAP, DEFAULT_MONSTER_AGGRO_RADIUS,
+    DEFAULT_MONSTER_COLOUR, DEFAULT_MONSTER_FACTION, DEFAULT_MONSTER_FLEE_HP_PERCENTAGE,
+    DEFAULT_MONSTER_ICON, DEFAULT_MONSTER_LEASH_RANGE, DEFAULT_MONSTER_POSITION_X,
+    DEFAULT_MONSTER_POSITION_Y, DEFAULT_MONSTER_PREFERRED_DISTANCE, DEFAULT_MONSTER_TYPE,
+    PLAYER_FACTION,
 };
 use crate::ecs::components::{DisplayDetails, Inventory, MonsterDetails, Position, Stats};
 use bincode::{Decode, Encode};
+use crossterm::style::Color;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -19,6 +23,176 @@ pub struct Monster {
     pub inventory: Inventory,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub enum BehaviourProfile {
+    #[default]
+    Aggressive,
+    PassiveUntilAttacked,
+    Cowardly,
+    StationaryGuard,
+    Patrol,
+    RangedKiter,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct MonsterBehaviour {
+    #[serde(default)]
+    pub profile: BehaviourProfile,
+    #[serde(default = "default_aggro_radius")]
+    pub aggro_radius: u32,
+    #[serde(default = "default_leash_range")]
+    pub leash_range: u32,
+    #[serde(default = "default_flee_hp_percentage")]
+    pub flee_hp_percentage: u8,
+    #[serde(default = "default_preferred_distance")]
+    pub preferred_distance: u32,
+    //offsets from the spawn position, visited in order
+    #[serde(default)]
+    pub patrol_waypoints: Vec<(i32, i32)>,
+}
+
+impl Default for MonsterBehaviour {
+    fn default() -> Self {
+        MonsterBehaviour {
+            profile: BehaviourProfile::default(),
+            aggro_radius: DEFAULT_MONSTER_AGGRO_RADIUS,
+            leash_range: DEFAULT_MONSTER_LEASH_RANGE,
+            flee_hp_percentage: DEFAULT_MONSTER_FLEE_HP_PERCENTAGE,
+            preferred_distance: DEFAULT_MONSTER_PREFERRED_DISTANCE,
+            patrol_waypoints: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct Faction {
+    #[serde(default = "default_faction_name")]
+    pub name: String,
+    //factions attacked on sight, any other faction is only fought after it attacks first
+    #[serde(default = "default_hostile_factions")]
+    pub hostile_to: Vec<String>,
+}
+
+impl Default for Faction {
+    fn default() -> Self {
+        Faction {
+            name: default_faction_name(),
+            hostile_to: default_hostile_factions(),
+        }
+    }
+}
+
+impl Faction {
+    #[must_use]
+    pub fn is_hostile_to(&self, faction: &str) -> bool {
+        !self.is_allied_with(faction) && self.hostile_to.iter().any(|hostile| hostile == faction)
+    }
+
+    #[must_use]
+    pub fn is_allied_with(&self, faction: &str) -> bool {
+        self.name == faction
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct BossDetails {
+    //ordered by hp threshold, highest first, the first phase starts when the boss spawns
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct BossPhase {
+    pub hp_threshold_percentage: u8,
+    #[serde(default)]
+    pub announcement: String,
+    #[serde(default = "default_ability_interval_ticks")]
+    pub ability_interval_ticks: u32,
+    #[serde(default)]
+    pub abilities: Vec<BossAbility>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub enum BossAbility {
+    AreaAttack { radius: u32, damage: u16 },
+    Summon { monster_type: String, count: u32 },
+    Enrage { str_bonus: u16, dex_bonus: u16 },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub enum EliteAffix {
+    Hardy,
+    Swift,
+    Fiery,
+}
+
+impl EliteAffix {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            EliteAffix::Hardy => "Hardy",
+            EliteAffix::Swift => "Swift",
+            EliteAffix::Fiery => "Fiery",
+        }
+    }
+
+    #[must_use]
+    pub fn colour(self) -> Color {
+        match self {
+            EliteAffix::Hardy => Color::DarkYellow,
+            EliteAffix::Swift => Color::Cyan,
+            EliteAffix::Fiery => Color::DarkRed,
+        }
+    }
+}
+
+impl MonsterDetails {
+    //elites have a single affix, champions have more than one
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        if self.elite_affixes.is_empty() {
+            return self.monster_type.clone();
+        }
+        let mut name = String::new();
+        if self.elite_affixes.len() > 1 {
+            name.push_str("Champion ");
+        }
+        for affix in &self.elite_affixes {
+            name.push_str(affix.name());
+            name.push(' ');
+        }
+        name.push_str(&self.monster_type);
+        name
+    }
+}
+
+fn default_faction_name() -> String {
+    DEFAULT_MONSTER_FACTION.to_string()
+}
+
+fn default_hostile_factions() -> Vec<String> {
+    vec![PLAYER_FACTION.to_string()]
+}
+
+fn default_ability_interval_ticks() -> u32 {
+    DEFAULT_BOSS_ABILITY_INTERVAL_TICKS
+}
+
+fn default_aggro_radius() -> u32 {
+    DEFAULT_MONSTER_AGGRO_RADIUS
+}
+
+fn default_leash_range() -> u32 {
+    DEFAULT_MONSTER_LEASH_RANGE
+}
+
+fn default_flee_hp_percentage() -> u8 {
+    DEFAULT_MONSTER_FLEE_HP_PERCENTAGE
+}
+
+fn default_preferred_distance() -> u32 {
+    DEFAULT_MONSTER_PREFERRED_DISTANCE
+}
+
 impl Default for Monster {
     fn default() -> Self {
         Monster {
@@ -33,7 +207,14 @@ impl Default for Monster {
                     velocity_x: 0,
                     velocity_y: 0,
                 },
+                spawn_index: None,
                 current_target: None,
+                behaviour: MonsterBehaviour::default(),
+                next_waypoint: 0,
+                boss: None,
+                summoned_by: None,
+                elite_affixes: vec![],
+                faction: Faction::default(),
             },
             display_details: DisplayDetails {
                 icon: DEFAULT_MONSTER_ICON,
@@ -52,6 +233,10 @@ impl Default for Monster {
                 update_available: false,
                 current_hp: 1.0,
                 max_hp: 1.0,
+                current_mana: 0.0,
+                max_mana: 0.0,
+                current_stamina: 0.0,
+                max_stamina: 0.0,
                 str: 1.0,
                 dex: 1.0,
                 con: 1.0,
@@ -60,6 +245,8 @@ impl Default for Monster {
                 exp: 0,
                 exp_next: 1000,
                 in_combat: false,
+                pvp_enabled: false,
+                pvp_toggle_cooldown: 0,
             },
             inventory: Inventory::default(),
         }
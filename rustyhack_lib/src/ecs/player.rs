@@ -21,6 +21,7 @@ impl Default for Player {
                 player_name: String::new(),
                 client_addr: String::new(),
                 currently_online: false,
+                respawn_position: None,
             },
             display_details: DisplayDetails::default(),
             position: Position {
@@ -35,6 +36,10 @@ impl Default for Player {
                 update_available: false,
                 current_hp: 50.0,
                 max_hp: 50.0,
+                current_mana: 20.0,
+                max_mana: 20.0,
+                current_stamina: 28.0,
+                max_stamina: 28.0,
                 str: 10.0,
                 dex: 10.0,
                 con: 10.0,
@@ -43,6 +48,8 @@ impl Default for Player {
                 exp: 0,
                 exp_next: 1000,
                 in_combat: false,
+                pvp_enabled: false,
+                pvp_toggle_cooldown: 0,
             },
             inventory: Inventory::default(),
         }
@@ -0,0 +1,74 @@
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct StatusEffect {
+    pub name: String,
+    //server ticks the effect lasts for
+    pub duration_ticks: u32,
+    //healing every tick, negative for damage over time like poison
+    #[serde(default)]
+    pub hp_per_tick: f32,
+    #[serde(default)]
+    pub stat_modifiers: Vec<StatModifier>,
+    //stunned entities can't move, or attack by moving into something
+    #[serde(default)]
+    pub prevents_movement: bool,
+    #[serde(default)]
+    pub stacking: StackingRule,
+    //harmful effects can be resisted, based on con
+    #[serde(default)]
+    pub harmful: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct StatModifier {
+    pub stat: ModifiedStat,
+    pub amount: f32,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub enum ModifiedStat {
+    Str,
+    Dex,
+    Con,
+}
+
+//what happens when an effect is applied to something that already has it
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Encode, Decode, Default)]
+pub enum StackingRule {
+    //start the duration again
+    #[default]
+    Refresh,
+    //add another stack up to the max, multiplying the effect, and start the duration again
+    Stack {
+        max_stacks: u32,
+    },
+    //leave the existing effect alone
+    Ignore,
+}
+
+//a chance of applying an effect whenever a weapon or ability hits
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct OnHitStatusEffect {
+    pub chance_percentage: f32,
+    pub effect: StatusEffect,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct ActiveStatusEffect {
+    pub effect: StatusEffect,
+    pub remaining_ticks: u32,
+    pub stacks: u32,
+}
+
+impl ActiveStatusEffect {
+    #[must_use]
+    pub fn display_text(&self) -> String {
+        let mut text = self.effect.name.clone();
+        if self.stacks > 1 {
+            text += &(" x".to_string() + &self.stacks.to_string());
+        }
+        text + " (" + &self.remaining_ticks.to_string() + ")"
+    }
+}
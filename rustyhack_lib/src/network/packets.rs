@@ -1,13 +1,17 @@
 use bincode::{Decode, Encode};
 use crossterm::style::Color;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::background_map::tiles::Tile;
 use crate::background_map::{AllMaps, AllMapsChunk};
-use crate::ecs::components::{Inventory, Position, Stats};
+use crate::ecs::ability::AllAbilities;
+use crate::ecs::components::{Inventory, Position, Stats, StatusEffects};
 use crate::ecs::player::Player;
 
 pub type EntityPositionBroadcast = HashMap<Uuid, (u32, u32, String, char, Color, String)>;
+pub type MapExitsBroadcast = HashMap<String, Vec<(u32, u32, String)>>;
 
 #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode)]
 pub enum PlayerRequest {
@@ -18,6 +22,11 @@ pub enum PlayerRequest {
     ChangeMap(PositionMessage),
     DropItem((u16, PositionMessage)),
     StatUp((String, String)),
+    Search(PositionMessage),
+    BuildBarricade((String, i32, i32)),
+    RangedAttack((String, u32, u32)),
+    UseAbility((String, String, Option<(u32, u32)>)),
+    TogglePvp(String),
     GetAllMaps,
     Timeout(String),
     Undefined,
@@ -25,7 +34,7 @@ pub enum PlayerRequest {
 
 #[derive(Debug, Encode, Decode)]
 pub enum ServerMessage {
-    PlayerJoined(Player),
+    PlayerJoined(Box<Player>),
     PlayerAlreadyOnline,
     AllMaps(AllMaps),
     AllMapsChunk(AllMapsChunk),
@@ -33,7 +42,14 @@ pub enum ServerMessage {
     UpdatePosition(Position),
     UpdateStats(Stats),
     UpdateInventory(Inventory),
+    UpdateStatusEffects(StatusEffects),
     UpdateOtherEntities(#[bincode(with_serde)] (Uuid, (u32, u32, String, char, Color, String))),
+    UpdateExploredTiles((String, Vec<(u32, u32)>)),
+    UpdateDiscoveredTraps((String, Vec<(u32, u32)>)),
+    UpdateTiles((String, Vec<(u32, u32, Tile)>)),
+    UpdateMapExits((String, Vec<(u32, u32, String)>)),
+    AbilityDefinitions(AllAbilities),
+    CombatEvent(CombatEvent),
     SystemMessage(SystemMessage),
 }
 
@@ -55,3 +71,28 @@ pub struct SystemMessage {
     #[bincode(with_serde)]
     pub colour: Option<Color>,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub enum AttackOutcome {
+    Missed,
+    Blocked,
+    Hit { damage: f32, critical: bool },
+}
+
+impl AttackOutcome {
+    #[must_use]
+    pub fn damage(self) -> f32 {
+        match self {
+            AttackOutcome::Hit { damage, .. } => damage,
+            AttackOutcome::Missed | AttackOutcome::Blocked => 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct CombatEvent {
+    pub attacker: String,
+    pub defender: String,
+    pub outcome: AttackOutcome,
+    pub killed: bool,
+}
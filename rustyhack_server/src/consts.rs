@@ -3,23 +3,77 @@ use std::time::Duration;
 pub(crate) const LOG_NAME: &str = "rustyhack_server.log";
 pub(crate) const WORLD_BACKUP_TMP_FILENAME: &str = "rustyhack_server_world_backup.tmp";
 pub(crate) const WORLD_BACKUP_FILENAME: &str = "rustyhack_server_world_backup.json";
+pub(crate) const COMBAT_LOG_FILENAME: &str = "rustyhack_server_combat_log.jsonl";
 pub(crate) const ENTITY_UPDATE_BROADCAST_TICK: Duration = Duration::from_millis(100);
 pub(crate) const SERVER_GAME_TICK: Duration = Duration::from_secs(2);
+pub(crate) const ACTION_TICK: Duration = Duration::from_millis(100);
+pub(crate) const BASE_ACTION_ENERGY_GAIN: f32 = 10.0;
+pub(crate) const MOVE_ENERGY_COST: f32 = 20.0;
+pub(crate) const ATTACK_ENERGY_COST: f32 = 200.0;
+pub(crate) const MONSTER_WANDER_CHANCE_PERCENTAGE: u32 = 10;
 pub(crate) const LOOP_TICK: Duration = Duration::from_millis(10);
+pub(crate) const GAME_DAY_LENGTH: Duration = Duration::from_mins(24);
+pub(crate) const GAME_NIGHT_LENGTH: Duration = Duration::from_mins(8);
 pub(crate) const SERVER_BACKUP_TICK: Duration = Duration::from_mins(1);
-pub(crate) const MONSTER_DISTANCE_ACTIVATION: i32 = 10;
+pub(crate) const PATHFINDING_MAX_SEARCH_NODES: u32 = 1000;
+pub(crate) const PATHFINDING_ENTITY_AVOIDANCE_DISTANCE: u32 = 3;
+pub(crate) const PATHFINDING_DESTINATION_TOLERANCE: u32 = 1;
+pub(crate) const THREAT_PER_ATTACK: f32 = 1.0;
+pub(crate) const THREAT_PER_DAMAGE: f32 = 1.0;
+//proximity threat settles at ten times this per tile of distance, well below a single hit
+pub(crate) const THREAT_PER_PROXIMITY: f32 = 0.1;
+pub(crate) const THREAT_DECAY_MULTIPLIER: f32 = 0.9;
+pub(crate) const THREAT_MINIMUM: f32 = 0.1;
+pub(crate) const THREAT_TARGET_SWITCH_MULTIPLIER: f32 = 1.1;
 pub(crate) const ASSETS_DIRECTORY: &str = "assets";
 pub(crate) const MAPS_DIRECTORY: &str = "maps";
 pub(crate) const MAP_EXITS_DIRECTORY: &str = "map_exits";
+pub(crate) const MAP_METADATA_DIRECTORY: &str = "map_metadata";
 pub(crate) const MONSTERS_DIRECTORY: &str = "monsters";
+pub(crate) const LOOT_TABLES_DIRECTORY: &str = "loot_tables";
 pub(crate) const SPAWNS_DIRECTORY: &str = "spawns";
-pub(crate) const TICK_SPAWN_CHANCE_PERCENTAGE: u32 = 5;
+pub(crate) const ABILITIES_DIRECTORY: &str = "abilities";
+pub(crate) const DEFAULT_RESPAWN_TICKS: u32 = 20;
+pub(crate) const MAX_LOOT_TABLE_DEPTH: u32 = 5;
+pub(crate) const MONSTER_STAT_SCALING_PER_LEVEL: f32 = 0.1;
+pub(crate) const DEFAULT_ELITE_CHANCE_PERCENTAGE: u32 = 5;
+pub(crate) const CHAMPION_CHANCE_PERCENTAGE: u32 = 20;
+pub(crate) const ELITE_LEVEL_BONUS_PER_AFFIX: u32 = 1;
+pub(crate) const HARDY_HP_MULTIPLIER: f32 = 1.5;
+pub(crate) const SWIFT_DEX_MULTIPLIER: f32 = 1.25;
+pub(crate) const SWIFT_WEAPON_SPEED_MULTIPLIER: f32 = 1.25;
+pub(crate) const FIERY_DAMAGE_BONUS: f32 = 3.0;
+pub(crate) const BOSS_MAX_LIVE_SUMMONS: usize = 6;
 pub(crate) const BASE_HEALTH_REGEN_PERCENT: f32 = 0.75;
 pub(crate) const HEALTH_REGEN_CON_PERCENT: f32 = 2.0;
 pub(crate) const HEALTH_REGEN_CON_STATIC_FACTOR: f32 = 5.0;
+pub(crate) const BASE_MAX_MANA: f32 = 20.0;
+pub(crate) const MAX_MANA_PER_LEVEL: f32 = 5.0;
+pub(crate) const BASE_MAX_STAMINA: f32 = 25.0;
+pub(crate) const MAX_STAMINA_PER_LEVEL: f32 = 5.0;
+pub(crate) const MANA_REGEN_PERCENT: f32 = 5.0;
+pub(crate) const STAMINA_REGEN_PERCENT: f32 = 15.0;
+pub(crate) const STATUS_EFFECT_RESISTANCE_PER_CON: f32 = 0.5;
+pub(crate) const MAX_STATUS_EFFECT_RESISTANCE_PERCENTAGE: f32 = 50.0;
 pub(crate) const MONSTER_EXP_MULTIPLICATION_FACTOR: u32 = 100;
 pub(crate) const EXP_LOSS_ON_DEATH_PERCENTAGE: u32 = 5;
 pub(crate) const GOLD_LOSS_ON_PVP_DEATH_PERCENTAGE: u32 = 5;
+pub(crate) const EXPLORED_TILES_PER_PACKET: usize = 500;
+pub(crate) const CORPSE_PROTECTION_TICKS: u32 = 150;
+pub(crate) const CORPSE_DECAY_TICKS: u32 = 900;
+pub(crate) const PVP_TOGGLE_COOLDOWN_TICKS: u32 = 30;
+pub(crate) const DARKNESS_ACCURACY_PENALTY: f32 = 25.0;
+pub(crate) const RANGED_ACCURACY_PENALTY_PER_TILE: f32 = 5.0;
+pub(crate) const BASE_CRITICAL_HIT_CHANCE_PERCENTAGE: f32 = 5.0;
+pub(crate) const CRITICAL_HIT_CHANCE_PER_DEX: f32 = 0.25;
+pub(crate) const CRITICAL_HIT_DAMAGE_MULTIPLIER: f32 = 1.5;
+pub(crate) const WATER_SLOW_CHANCE_PERCENTAGE: u32 = 50;
+pub(crate) const LAVA_DAMAGE_PER_TICK: f32 = 15.0;
+pub(crate) const ACID_DAMAGE_PER_TICK: f32 = 8.0;
+pub(crate) const TRAP_DAMAGE: f32 = 10.0;
+pub(crate) const SEARCH_RADIUS: i32 = 2;
+pub(crate) const SEARCH_BASE_CHANCE_PERCENTAGE: f32 = 50.0;
+pub(crate) const BREAKABLE_WALL_HIT_POINTS: f32 = 60.0;
 
 /*
 The base exp table is based on the following formula:
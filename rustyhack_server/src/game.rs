@@ -1,12 +1,17 @@
+mod abilities;
+mod action_energy;
 mod backup;
 pub(super) mod combat;
+mod combat_log;
 mod ecs;
 mod map;
 mod monsters;
 mod player_message_handler;
-mod players;
+pub(super) mod players;
+mod rng;
+mod status_effects;
+mod time_of_day;
 
-use std::collections::HashMap;
 use std::thread;
 use std::time::Instant;
 
@@ -18,32 +23,42 @@ use legion::Resources;
 use message_io::node::{NodeHandler, NodeListener};
 
 use crate::consts;
-use crate::game::combat::{CombatAttackerStats, CombatParties};
+use crate::game::abilities::PendingAbilities;
+use crate::game::action_energy::ActionEnergy;
+use crate::game::combat::{CombatAttackerStats, CombatParties, PendingRangedAttacks};
+use crate::game::combat_log::CombatLog;
 use crate::game::map::exits;
 use crate::network_messages::{map_sender, packet_receiver};
+use map::spawns::AllSpawnTimers;
 use map::state::EntityPositionMap;
-use map::{spawns, state, tiles};
-use players::PlayersPositions;
+use map::terrain::{PendingTileChanges, TerrainAttacks, TerrainDamage};
+use map::{metadata, spawns, state, terrain, tiles};
+use monsters::MonstersPositions;
+use monsters::bosses::{DismissedSummons, PendingAnnouncements, PendingAreaAttacks};
+use monsters::loot;
+use monsters::pathfinding::MonsterPaths;
+use monsters::threat::ThreatTables;
+use players::{DeathPenaltyMode, PlayersPositions};
 
 pub(super) fn run(
     sender: &Sender<Packet>,
     receiver: Receiver<SocketEvent>,
     tcp_handler: NodeHandler<()>,
     tcp_listener: NodeListener<()>,
+    combat_log_enabled: bool,
+    death_penalty_mode: DeathPenaltyMode,
+    rng_seed: Option<u64>,
 ) {
     //initialise all basic resources
-    let all_maps = tiles::initialise_all_maps();
-    let all_maps_resource = all_maps.clone();
-    let all_map_states = state::initialise_all_map_states(&all_maps);
-    let combat_parties: CombatParties = HashMap::new();
-    let combat_attacker_stats: CombatAttackerStats = HashMap::new();
-    let players_positions: PlayersPositions = HashMap::new();
-    let entity_position_map: EntityPositionMap = HashMap::new();
+    let all_map_metadata = metadata::initialise_all_map_metadata();
+    let all_maps = tiles::initialise_all_maps(&all_map_metadata);
+    let mut all_maps_resource = all_maps.clone();
     let all_monster_definitions = monsters::initialise_all_monster_definitions();
-    let (default_spawn_counts, all_spawns_map) = spawns::initialise_all_spawn_definitions();
-    let all_map_exits = exits::initialise_all_map_exit_definitions();
+    let all_spawns_map = spawns::initialise_all_spawn_definitions();
+    let mut game_rng = rng::initialise_game_rng(rng_seed);
     let registry = backup::create_world_registry();
     let mut player_update_schedule = systems::build_player_update_schedule();
+    let mut action_tick_update_schedule = systems::build_action_tick_update_schedule();
     let mut server_tick_update_schedule = systems::build_server_tick_update_schedule();
     let mut map_state_update_schedule = systems::build_map_state_update_schedule();
     let mut health_regen_schedule = systems::build_health_regen_schedule();
@@ -58,31 +73,41 @@ pub(super) fn run(
     //initialise tcp map sender thread
     map_sender::spawn_map_sender_thread(tcp_handler, tcp_listener, all_maps);
 
-    //load resources into world
-    let mut resources = Resources::default();
-    resources.insert(all_maps_resource);
-    resources.insert(all_map_states);
-    resources.insert(combat_parties);
-    resources.insert(combat_attacker_stats);
-    resources.insert(players_positions);
-    resources.insert(sender.clone());
-    resources.insert(all_spawns_map.clone());
-    resources.insert(default_spawn_counts);
-    resources.insert(all_monster_definitions.clone());
-    resources.insert(entity_position_map);
-    info!("Finished loading resources.");
-
-    let (mut world, is_saved_world) =
-        backup::load_world(&registry, &all_monster_definitions, &all_spawns_map);
+    let (mut world, is_saved_world) = backup::load_world(
+        &registry,
+        &all_monster_definitions,
+        &all_spawns_map,
+        &all_maps_resource,
+        &mut game_rng,
+    );
     info!("Finished initialising ECS World.");
 
     if is_saved_world {
         //marking all players as logged out on initial server start
         common_player::logout_all_players(&mut world);
+        //clients are sent these changes when joining, the map sender only has the original maps
+        terrain::apply_saved_tile_changes(&mut world, &mut all_maps_resource);
     }
 
+    //load resources into world
+    let mut resources = Resources::default();
+    resources.insert(state::initialise_all_map_states(&all_maps_resource));
+    resources.insert(all_maps_resource);
+    resources.insert(sender.clone());
+    resources.insert(all_spawns_map);
+    resources.insert(all_monster_definitions);
+    resources.insert(loot::initialise_all_loot_tables());
+    resources.insert(abilities::initialise_all_abilities());
+    resources.insert(exits::initialise_all_map_exit_definitions());
+    resources.insert(CombatLog::new(combat_log_enabled));
+    resources.insert(death_penalty_mode);
+    resources.insert(game_rng);
+    insert_transient_resources(&mut resources);
+    info!("Finished loading resources.");
+
     //start tick counts
     let mut entity_update_broadcast_tick_time = Instant::now();
+    let mut action_tick_time = Instant::now();
     let mut server_game_tick_time = Instant::now();
     let mut server_backup_tick_time = Instant::now();
     let mut loop_tick_time = Instant::now();
@@ -93,7 +118,7 @@ pub(super) fn run(
         //process player updates as soon as they are received
         if player_message_handler::process_player_messages(
             &mut world,
-            &all_map_exits,
+            &resources,
             &channel_receiver,
             sender,
         ) {
@@ -106,6 +131,15 @@ pub(super) fn run(
             send_network_messages_schedule.execute(&mut world, &mut resources);
         }
 
+        //movement and combat for all entities, limited by their action energy
+        if action_tick_time.elapsed() >= consts::ACTION_TICK {
+            debug!("Executing action tick schedule...");
+            map_state_update_schedule.execute(&mut world, &mut resources);
+            action_tick_update_schedule.execute(&mut world, &mut resources);
+            send_network_messages_schedule.execute(&mut world, &mut resources);
+            action_tick_time = Instant::now();
+        }
+
         //all other updates that depend on the server game tick
         if server_game_tick_time.elapsed() >= consts::SERVER_GAME_TICK {
             server_game_tick_count += 1;
@@ -138,24 +172,49 @@ pub(super) fn run(
             server_backup_tick_time = Instant::now();
         }
 
-        //snapshotting the duration here to prevent a possible server crash
-        let loop_tick_time_elapsed = loop_tick_time.elapsed();
-        if loop_tick_time_elapsed >= consts::LOOP_TICK {
-            warn!(
-                "Loop took longer than specified tick time, expected: {}ms, actual: {}ms",
-                consts::LOOP_TICK.as_millis(),
-                loop_tick_time_elapsed.as_millis()
-            );
-            loop_tick_time = Instant::now();
-            continue;
-        }
-        let duration_to_sleep = consts::LOOP_TICK
-            .checked_sub(loop_tick_time_elapsed)
-            .unwrap();
-        if duration_to_sleep.as_nanos() > 0 {
-            //sleep here for LOOP_TICK so we don't hammer the CPU unnecessarily
-            thread::sleep(duration_to_sleep);
-        }
-        loop_tick_time = Instant::now();
+        wait_for_next_loop_tick(&mut loop_tick_time);
     }
 }
+
+fn wait_for_next_loop_tick(loop_tick_time: &mut Instant) {
+    //snapshotting the duration here to prevent a possible server crash
+    let loop_tick_time_elapsed = loop_tick_time.elapsed();
+    if loop_tick_time_elapsed >= consts::LOOP_TICK {
+        warn!(
+            "Loop took longer than specified tick time, expected: {}ms, actual: {}ms",
+            consts::LOOP_TICK.as_millis(),
+            loop_tick_time_elapsed.as_millis()
+        );
+        *loop_tick_time = Instant::now();
+        return;
+    }
+    let duration_to_sleep = consts::LOOP_TICK
+        .checked_sub(loop_tick_time_elapsed)
+        .unwrap();
+    if duration_to_sleep.as_nanos() > 0 {
+        //sleep here for LOOP_TICK so we don't hammer the CPU unnecessarily
+        thread::sleep(duration_to_sleep);
+    }
+    *loop_tick_time = Instant::now();
+}
+
+//resources that start empty and are rebuilt as the game runs, so aren't backed up
+fn insert_transient_resources(resources: &mut Resources) {
+    resources.insert(CombatParties::new());
+    resources.insert(CombatAttackerStats::new());
+    resources.insert(PlayersPositions::new());
+    resources.insert(MonstersPositions::new());
+    resources.insert(EntityPositionMap::new());
+    resources.insert(TerrainAttacks::new());
+    resources.insert(TerrainDamage::new());
+    resources.insert(PendingTileChanges::new());
+    resources.insert(MonsterPaths::new());
+    resources.insert(ThreatTables::new());
+    resources.insert(ActionEnergy::new());
+    resources.insert(PendingAreaAttacks::new());
+    resources.insert(PendingRangedAttacks::new());
+    resources.insert(PendingAbilities::default());
+    resources.insert(PendingAnnouncements::new());
+    resources.insert(DismissedSummons::new());
+    resources.insert(AllSpawnTimers::new());
+}
@@ -0,0 +1,126 @@
+use crate::consts;
+use crate::game::combat::Attacker;
+use rustyhack_lib::ecs::ability::{Ability, AbilityCost, AllAbilities};
+use rustyhack_lib::ecs::components::{AbilityTimers, MonsterDetails, Position, Stats};
+use rustyhack_lib::utils::file;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::process;
+use uuid::Uuid;
+
+#[derive(Debug, Default)]
+pub(super) struct PendingAbilities {
+    //ability name and the tile targeted, if the ability needs one
+    pub(super) requests: HashMap<Uuid, (String, Option<(u32, u32)>)>,
+    pub(super) area_attacks: Vec<AbilityAreaAttack>,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct AbilityAreaAttack {
+    pub(super) attacker: Attacker,
+    //set for monsters, so only their enemies are hit
+    pub(super) attacker_monster: Option<MonsterDetails>,
+    pub(super) position: Position,
+    pub(super) radius: u32,
+}
+
+pub(super) fn initialise_all_abilities() -> AllAbilities {
+    info!("About to initialise all abilities");
+    let mut all_abilities: AllAbilities = vec![];
+    let mut file_location = file::current_exe_location();
+    file_location.pop();
+    file_location.push(consts::ASSETS_DIRECTORY);
+    file_location.push(consts::ABILITIES_DIRECTORY);
+    let paths = file::get_all_files_in_location(&file_location);
+    for path in paths {
+        let ability = get_ability_from_path(&path.unwrap().path());
+        info!("Initialised ability: {:?}", &ability.name);
+        all_abilities.push(ability);
+    }
+    check_ability_names_are_unique(&all_abilities);
+    //clients list abilities in this order, so it needs to be the same every time
+    all_abilities.sort_by(|a, b| a.min_level.cmp(&b.min_level).then(a.name.cmp(&b.name)));
+    all_abilities
+}
+
+fn get_ability_from_path(path: &Path) -> Ability {
+    let file = File::open(path).unwrap_or_else(|err| {
+        error!(
+            "Problem getting ability from file: {}, error: {err}",
+            path.display()
+        );
+        process::exit(1);
+    });
+    let buf_reader = BufReader::new(file);
+    serde_json::from_reader(buf_reader).unwrap_or_else(|err| {
+        error!(
+            "Problem deserializing ability from file: {}, error: {err}",
+            path.display()
+        );
+        process::exit(1);
+    })
+}
+
+fn check_ability_names_are_unique(all_abilities: &AllAbilities) {
+    let mut ability_names = HashSet::new();
+    for ability in all_abilities {
+        if !ability_names.insert(&ability.name) {
+            error!("Ability {} is defined more than once.", ability.name);
+            process::exit(1);
+        }
+    }
+}
+
+/*
+Max mana = Base mana + (Mana per level * (Level - 1))
+Max stamina = (Base stamina + (Stamina per level * (Level - 1))) * ((Con / 100) + 1)
+
+Recalculated whenever level or con changes, and on joining for characters from older backups.
+*/
+pub(super) fn update_max_resources(stats: &mut Stats) {
+    let levels_gained = f32::from(u16::try_from(stats.level - 1).unwrap_or(u16::MAX));
+    stats.max_mana = (consts::BASE_MAX_MANA + consts::MAX_MANA_PER_LEVEL * levels_gained).round();
+    stats.max_stamina = ((consts::BASE_MAX_STAMINA
+        + consts::MAX_STAMINA_PER_LEVEL * levels_gained)
+        * (1.0 + (stats.con / 100.0)))
+        .round();
+    stats.current_mana = stats.current_mana.min(stats.max_mana);
+    stats.current_stamina = stats.current_stamina.min(stats.max_stamina);
+}
+
+pub(super) fn can_afford(stats: &Stats, cost: AbilityCost) -> bool {
+    match cost {
+        AbilityCost::Mana(amount) => stats.current_mana >= amount,
+        AbilityCost::Stamina(amount) => stats.current_stamina >= amount,
+    }
+}
+
+pub(super) fn spend_cost(stats: &mut Stats, cost: AbilityCost) {
+    match cost {
+        AbilityCost::Mana(amount) => stats.current_mana -= amount,
+        AbilityCost::Stamina(amount) => stats.current_stamina -= amount,
+    }
+}
+
+pub(super) fn is_on_cooldown(ability_timers: &AbilityTimers, ability_name: &str) -> bool {
+    ability_timers.cooldowns.contains_key(ability_name)
+}
+
+pub(super) fn start_cooldown(ability_timers: &mut AbilityTimers, ability: &Ability) {
+    if ability.cooldown_ticks > 0 {
+        ability_timers
+            .cooldowns
+            .insert(ability.name.clone(), ability.cooldown_ticks);
+    }
+}
+
+pub(super) fn update_cooldowns(ability_timers: &mut AbilityTimers) {
+    for remaining_ticks in ability_timers.cooldowns.values_mut() {
+        *remaining_ticks = remaining_ticks.saturating_sub(1);
+    }
+    ability_timers
+        .cooldowns
+        .retain(|_ability_name, remaining_ticks| *remaining_ticks > 0);
+}
@@ -0,0 +1,48 @@
+use crate::consts::{ATTACK_ENERGY_COST, BASE_ACTION_ENERGY_GAIN, MOVE_ENERGY_COST};
+use rustyhack_lib::ecs::components::{Inventory, Stats};
+use rustyhack_lib::ecs::inventory::Weapon;
+use rustyhack_lib::ecs::item::get_ranged_weapon;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/*
+Every entity builds up action energy each action tick, and spends it to move or attack.
+
+Energy gained per tick = Base energy gain * ((Dex / 100) + 1)
+Move cost = Flat figure
+Attack cost = Flat figure / Weapon speed
+
+This gives the attack speed worked out by Weapon::attack_speed.
+*/
+
+pub(super) type ActionEnergy = HashMap<Uuid, f32>;
+
+pub(super) fn calculate_energy_gain(stats: &Stats) -> f32 {
+    BASE_ACTION_ENERGY_GAIN * ((stats.dex / 100.0) + 1.0)
+}
+
+pub(super) fn calculate_attack_cost(inventory: &Inventory) -> f32 {
+    calculate_weapon_attack_cost(&inventory.equipped.weapon)
+}
+
+fn calculate_weapon_attack_cost(weapon: &Weapon) -> f32 {
+    ATTACK_ENERGY_COST / weapon.speed.max(f32::EPSILON)
+}
+
+//energy can only build up to what's needed for the most expensive action, so it can't be banked
+pub(super) fn calculate_max_energy(inventory: &Inventory) -> f32 {
+    let ranged_attack_cost = get_ranged_weapon(inventory).map_or(0.0, calculate_weapon_attack_cost);
+    calculate_attack_cost(inventory)
+        .max(ranged_attack_cost)
+        .max(MOVE_ENERGY_COST)
+}
+
+pub(super) fn has_energy_for(action_energy: &ActionEnergy, id: &Uuid, cost: f32) -> bool {
+    action_energy.get(id).is_some_and(|energy| *energy >= cost)
+}
+
+pub(super) fn spend_energy(action_energy: &mut ActionEnergy, id: &Uuid, cost: f32) {
+    if let Some(energy) = action_energy.get_mut(id) {
+        *energy -= cost;
+    }
+}
@@ -1,14 +1,17 @@
 use crate::consts::{WORLD_BACKUP_FILENAME, WORLD_BACKUP_TMP_FILENAME};
 use crate::game::map::spawns::AllSpawnsMap;
 use crate::game::monsters::spawning::spawn_initial_monsters;
+use crate::game::rng::GameRng;
 use crossterm::style::Color;
 use legion::serialize::Canon;
 use legion::{Registry, World};
+use rustyhack_lib::background_map::AllMaps;
 use rustyhack_lib::ecs::components::{
-    DisplayDetails, Inventory, ItemDetails, MonsterDetails, PlayerDetails, Position, Stats,
+    AbilityTimers, BossState, DisplayDetails, ExploredMaps, Inventory, ItemDetails, MonsterDetails,
+    PlayerDetails, Position, Stats, StatusEffects, TileChange,
 };
 use rustyhack_lib::ecs::inventory::{Armour, Equipment, Trinket, Weapon};
-use rustyhack_lib::ecs::item::Item;
+use rustyhack_lib::ecs::item::{Corpse, Item};
 use rustyhack_lib::ecs::monster::{AllMonsterDefinitions, Monster};
 use rustyhack_lib::ecs::player::Player;
 use rustyhack_lib::utils::file;
@@ -29,10 +32,16 @@ pub(super) fn create_world_registry() -> Registry<String> {
     registry.register::<Stats>("stats".to_string());
     registry.register::<Inventory>("inventory".to_string());
     registry.register::<Position>("position".to_string());
+    registry.register::<ExploredMaps>("explored_maps".to_string());
+    registry.register::<AbilityTimers>("ability_timers".to_string());
+    registry.register::<StatusEffects>("status_effects".to_string());
+    registry.register::<TileChange>("tile_change".to_string());
+    registry.register::<BossState>("boss_state".to_string());
     registry.register::<Equipment>("equipment".to_string());
     registry.register::<Player>("player".to_string());
     registry.register::<Monster>("monster".to_string());
     registry.register::<Item>("item".to_string());
+    registry.register::<Corpse>("corpse".to_string());
     registry.register::<Weapon>("weapon".to_string());
     registry.register::<Armour>("armour".to_string());
     registry.register::<Trinket>("trinket".to_string());
@@ -79,6 +88,8 @@ pub(super) fn load_world(
     registry: &Registry<String>,
     all_monster_definitions: &AllMonsterDefinitions,
     all_spawns_map: &AllSpawnsMap,
+    all_maps: &AllMaps,
+    rng: &mut GameRng,
 ) -> (World, bool) {
     let mut world;
     let mut is_saved_world = false;
@@ -101,7 +112,13 @@ pub(super) fn load_world(
         info!("Created new world successfully.");
 
         //spawn initial monsters
-        spawn_initial_monsters(&mut world, all_monster_definitions, all_spawns_map);
+        spawn_initial_monsters(
+            &mut world,
+            all_monster_definitions,
+            all_spawns_map,
+            all_maps,
+            rng,
+        );
         info!("Spawned all monsters in initial positions.");
     }
     (world, is_saved_world)
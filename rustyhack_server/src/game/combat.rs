@@ -1,5 +1,13 @@
+use crate::consts::{
+    BASE_CRITICAL_HIT_CHANCE_PERCENTAGE, CRITICAL_HIT_CHANCE_PER_DEX,
+    CRITICAL_HIT_DAMAGE_MULTIPLIER, RANGED_ACCURACY_PENALTY_PER_TILE,
+};
+use crate::game::rng::GameRng;
 use rand::RngExt;
+use rustyhack_lib::background_map::BackgroundMap;
 use rustyhack_lib::ecs::components::{Inventory, Stats};
+use rustyhack_lib::ecs::item::{get_carried_block_chance, get_carried_digging_power};
+use rustyhack_lib::network::packets::AttackOutcome;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::ops::Range;
@@ -17,17 +25,24 @@ Strength - Increases weapon damage by %
 Dexterity - Increases accuracy / dodge / weapon speed / entity speed
 Constitution - Increases HP, resistance to status effects
 Armour - Reduces incoming damage by %
+Resistances - Armour reduces damage of certain types (slashing, piercing, blunt, fire, poison) by a further %
+Shield - Chance to block an attack outright, only needs to be carried
 
 Calculated stats:
-Damage dealt = Weapon damage * ((Str / 100) + 1)
-Attack speed = Weapon speed * ((Dex / 100) + 1)
-Actual Damage received = Damage dealt * (1 - (Armour / 100))
-Accuracy% = Base accuracy + ((100 - base accuracy) * (Attacker's Dex / 100)) - ((100 - base accuracy) * (Defender's Dex / 100))
+Damage dealt = Weapon damage * ((Str / 100) + 1) * (Critical multiplier on a critical hit)
+Critical hit% = Base critical chance + (Critical chance per dex * Attacker's Dex)
+Attack speed = Weapon::attack_speed(Dex)
+Actual Damage received = Damage dealt * (1 - (Armour / 100)) * (1 - (Resistance to weapon damage type / 100))
+Accuracy% = Base accuracy + ((100 - base accuracy) * (Attacker's Dex / 100)) - ((100 - base accuracy) * (Defender's Dex / 100)) - Range penalty
+Range penalty = Penalty per tile * (Distance to defender - 1)
 
 */
 
 pub(super) type CombatParties = HashMap<Attacker, Defender>;
-pub(super) type CombatAttackerStats = HashMap<Uuid, (Stats, Inventory)>;
+//attacker stats and inventory at the time of the attack, along with the distance attacked from
+pub(super) type CombatAttackerStats = HashMap<Uuid, (Stats, Inventory, u32)>;
+//target tile on the attacker's current map, keyed by attacker id
+pub(super) type PendingRangedAttacks = HashMap<Uuid, (u32, u32)>;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Eq, Hash)]
 pub(crate) struct Attacker {
@@ -59,56 +74,137 @@ impl Default for Defender {
     }
 }
 
+pub(crate) struct Combatant {
+    pub(crate) is_player: bool,
+    pub(crate) pvp_enabled: bool,
+    pub(crate) tile: (u32, u32),
+}
+
+//players can't fight or be attacked in a safe zone, and only fight each other when both
+//have pvp turned on, the error is the reason shown to an attacking player
+pub(crate) fn check_combat_allowed(
+    current_map: &BackgroundMap,
+    attacker: &Combatant,
+    defender: &Combatant,
+) -> Result<(), &'static str> {
+    if (attacker.is_player || defender.is_player)
+        && (current_map.is_safe_zone(attacker.tile) || current_map.is_safe_zone(defender.tile))
+    {
+        return Err("You can't fight in a safe zone.");
+    }
+    if attacker.is_player && defender.is_player {
+        if !attacker.pvp_enabled {
+            return Err("You need to turn on PvP to attack other players.");
+        }
+        if !defender.pvp_enabled {
+            return Err("That player doesn't have PvP turned on.");
+        }
+    }
+    Ok(())
+}
+
 pub(super) fn resolve_combat(
     attacker_stats: &Stats,
     attacker_inventory: &Inventory,
     defender_stats: &Stats,
     defender_inventory: &Inventory,
-) -> f32 {
+    attack_distance: u32,
+    rng: &mut GameRng,
+) -> AttackOutcome {
     debug!("Resolving combat...");
-    if check_attack_success(
+    if !check_attack_success(
         attacker_stats.dex,
         attacker_inventory.equipped.weapon.accuracy,
         defender_stats.dex,
+        attack_distance,
+        rng,
     ) {
-        debug!("Attack hit...");
-        let actual_damage_received = calculate_actual_damage_received(
-            calculate_damage_dealt(
-                &attacker_inventory.equipped.weapon.damage_range,
-                attacker_stats.str,
-            ),
-            defender_inventory
-                .equipped
-                .armour
-                .damage_reduction_percentage,
-        );
-        debug!("Damage taken: {actual_damage_received}");
-        actual_damage_received
-    } else {
         debug!("Attack missed...");
-        0.0
+        return AttackOutcome::Missed;
+    }
+    if check_block_success(get_carried_block_chance(&defender_inventory.carried), rng) {
+        debug!("Attack blocked...");
+        return AttackOutcome::Blocked;
+    }
+    debug!("Attack hit...");
+    let critical = check_critical_hit(attacker_stats.dex, rng);
+    let mut damage_dealt = calculate_damage_dealt(
+        &attacker_inventory.equipped.weapon.damage_range,
+        attacker_stats.str,
+        rng,
+    );
+    if critical {
+        debug!("Critical hit!");
+        damage_dealt *= CRITICAL_HIT_DAMAGE_MULTIPLIER;
     }
+    let defender_armour = &defender_inventory.equipped.armour;
+    let actual_damage_received = calculate_actual_damage_received(
+        damage_dealt,
+        defender_armour.damage_reduction_percentage,
+        defender_armour.get_resistance(attacker_inventory.equipped.weapon.damage_type),
+    );
+    debug!("Damage taken: {actual_damage_received}");
+    AttackOutcome::Hit {
+        damage: actual_damage_received.round().max(0.0),
+        critical,
+    }
+}
+
+pub(super) fn resolve_terrain_damage(
+    attacker_stats: &Stats,
+    attacker_inventory: &Inventory,
+    rng: &mut GameRng,
+) -> f32 {
+    //terrain can't dodge or wear armour, but digging tools make short work of it
+    let damage_dealt = calculate_damage_dealt(
+        &attacker_inventory.equipped.weapon.damage_range,
+        attacker_stats.str,
+        rng,
+    ) + f32::from(get_carried_digging_power(&attacker_inventory.carried));
+    debug!("Terrain damage dealt: {damage_dealt}");
+    damage_dealt
 }
 
-fn calculate_damage_dealt(attacker_weapon_damage_range: &Range<f32>, attacker_str: f32) -> f32 {
-    let mut rng = rand::rng();
+fn calculate_damage_dealt(
+    attacker_weapon_damage_range: &Range<f32>,
+    attacker_str: f32,
+    rng: &mut GameRng,
+) -> f32 {
     let attacker_weapon_damage = rng.random_range(attacker_weapon_damage_range.clone());
     debug!("Weapon damage before strength modifier: {attacker_weapon_damage}");
     attacker_weapon_damage * ((attacker_str / 100.0) + 1.0)
 }
 
-fn calculate_actual_damage_received(damage_dealt: f32, defender_armour: f32) -> f32 {
-    damage_dealt * (1.0 - (defender_armour / 100.0))
+fn calculate_actual_damage_received(
+    damage_dealt: f32,
+    defender_armour: f32,
+    defender_resistance: f32,
+) -> f32 {
+    damage_dealt * (1.0 - (defender_armour / 100.0)) * (1.0 - (defender_resistance / 100.0))
+}
+
+fn check_critical_hit(attacker_dex: f32, rng: &mut GameRng) -> bool {
+    let critical_chance =
+        BASE_CRITICAL_HIT_CHANCE_PERCENTAGE + CRITICAL_HIT_CHANCE_PER_DEX * attacker_dex;
+    critical_chance >= rng.random_range(0.0..=100.0)
+}
+
+fn check_block_success(block_chance: f32, rng: &mut GameRng) -> bool {
+    block_chance > 0.0 && block_chance >= rng.random_range(0.0..=100.0)
 }
 
 fn check_attack_success(
     attacker_dex: f32,
     attacker_weapon_accuracy: f32,
     defender_dex: f32,
+    attack_distance: u32,
+    rng: &mut GameRng,
 ) -> bool {
-    let mut rng = rand::rng();
+    let range_penalty = RANGED_ACCURACY_PENALTY_PER_TILE
+        * f32::from(u16::try_from(attack_distance.saturating_sub(1)).unwrap_or(u16::MAX));
     let combat_accuracy = attacker_weapon_accuracy
         + ((100.0 - attacker_weapon_accuracy) * (attacker_dex / 100.0))
-        - ((100.0 - attacker_weapon_accuracy) * (defender_dex / 100.0));
+        - ((100.0 - attacker_weapon_accuracy) * (defender_dex / 100.0))
+        - range_penalty;
     combat_accuracy >= rng.random_range(0.0..=100.0)
 }
@@ -0,0 +1,77 @@
+use crate::consts::COMBAT_LOG_FILENAME;
+use rustyhack_lib::network::packets::CombatEvent;
+use rustyhack_lib::utils::file;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{LineWriter, Write};
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize)]
+pub(crate) struct LoggedCombatEvent {
+    pub(crate) timestamp_ms: u64,
+    pub(crate) map: String,
+    #[serde(flatten)]
+    pub(crate) event: CombatEvent,
+    pub(crate) defender_is_boss: bool,
+}
+
+//combat events from the current tick, optionally written out as json lines when processed
+#[derive(Default)]
+pub(crate) struct CombatLog {
+    pub(crate) events: Vec<LoggedCombatEvent>,
+    file: Option<LineWriter<File>>,
+}
+
+impl CombatLog {
+    pub(crate) fn new(write_to_file: bool) -> Self {
+        if !write_to_file {
+            return CombatLog::default();
+        }
+        let mut file_location = file::current_exe_location();
+        file_location.pop();
+        file_location.push(COMBAT_LOG_FILENAME);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(file_location.as_path())
+            .unwrap_or_else(|err| {
+                error!("Unable to open combat log file: {err}");
+                process::exit(1);
+            });
+        info!("Writing combat log to: {}", file_location.display());
+        CombatLog {
+            events: vec![],
+            file: Some(LineWriter::new(file)),
+        }
+    }
+
+    pub(crate) fn record(&mut self, map: &str, event: CombatEvent, defender_is_boss: bool) {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| {
+                u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+            });
+        self.events.push(LoggedCombatEvent {
+            timestamp_ms,
+            map: map.to_string(),
+            event,
+            defender_is_boss,
+        });
+    }
+
+    pub(crate) fn write_events(&mut self) {
+        for logged_event in self.events.drain(..) {
+            debug!("Combat event: {logged_event:?}");
+            let Some(file) = self.file.as_mut() else {
+                continue;
+            };
+            let result = serde_json::to_writer(&mut *file, &logged_event)
+                .map_err(std::io::Error::from)
+                .and_then(|()| file.write_all(b"\n"));
+            if let Err(err) = result {
+                warn!("Failed to write combat event to combat log: {err}");
+            }
+        }
+    }
+}
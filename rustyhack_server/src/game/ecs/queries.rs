@@ -1,3 +1,4 @@
+pub(crate) mod build_barricade;
 pub(crate) mod change_map;
 pub(crate) mod common_player;
 pub(crate) mod drop_item;
@@ -5,3 +6,7 @@ pub(crate) mod increase_stat;
 pub(crate) mod pickup_item;
 pub(crate) mod player_joined;
 pub(crate) mod player_left;
+pub(crate) mod ranged_attack;
+pub(crate) mod search;
+pub(crate) mod toggle_pvp;
+pub(crate) mod use_ability;
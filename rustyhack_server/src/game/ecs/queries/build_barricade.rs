@@ -0,0 +1,116 @@
+use crate::game::map::state;
+use crate::game::map::state::AllMapStates;
+use crate::game::map::terrain::PendingTileChanges;
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use laminar::Packet;
+use legion::{IntoQuery, World};
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::background_map::tiles::Tile;
+use rustyhack_lib::consts::{BARRICADE_GOLD_COST, BARRICADE_TILE_CHARACTER};
+use rustyhack_lib::ecs::components::{
+    Inventory, ItemDetails, PlayerDetails, Position, Stats, TileChange,
+};
+use rustyhack_lib::utils::math::{i32_from, u32_from};
+use std::collections::HashSet;
+
+#[allow(clippy::similar_names)]
+pub(crate) fn build_barricade(
+    world: &mut World,
+    all_maps: &AllMaps,
+    all_map_states: &mut AllMapStates,
+    pending_tile_changes: &mut PendingTileChanges,
+    build_details: &(String, i32, i32),
+    sender: &Sender<Packet>,
+) {
+    let (player_name, direction_x, direction_y) = build_details;
+    if !(-1..=1).contains(direction_x)
+        || !(-1..=1).contains(direction_y)
+        || (*direction_x == 0 && *direction_y == 0)
+    {
+        warn!("Invalid barricade direction received from player {player_name}.");
+        return;
+    }
+
+    //items on the floor would be lost underneath a barricade
+    let mut item_query = <(&ItemDetails, &Position)>::query();
+    let item_positions: HashSet<(String, u32, u32)> = item_query
+        .iter(world)
+        .filter(|(item_details, _item_position)| !item_details.has_been_picked_up)
+        .map(|(_item_details, item_position)| {
+            (
+                item_position.current_map.clone(),
+                item_position.pos_x,
+                item_position.pos_y,
+            )
+        })
+        .collect();
+
+    let mut build_position: Option<(String, u32, u32)> = None;
+    let mut query = <(&PlayerDetails, &Position, &Stats, &Inventory)>::query();
+    for (player_details, position, stats, inventory) in query.iter(world) {
+        if player_details.player_name == *player_name
+            && player_details.currently_online
+            && stats.current_hp > 0.0
+        {
+            let build_x = i32_from(position.pos_x) + direction_x;
+            let build_y = i32_from(position.pos_y) + direction_y;
+            let current_map = state::get_current_map(all_maps, &position.current_map);
+            let message = if build_x < 0
+                || build_y < 0
+                || !current_map.is_within_boundary(u32_from(build_y), u32_from(build_x))
+                || *current_map.get_tile_at(u32_from(build_y), u32_from(build_x))
+                    != Tile::EmptySpace
+            {
+                "You can't build a barricade there."
+            } else if inventory.gold < BARRICADE_GOLD_COST {
+                "You don't have enough gold to build a barricade."
+            } else if state::is_colliding_with_entity(
+                u32_from(build_x),
+                u32_from(build_y),
+                state::get_current_map_states(all_map_states, &position.current_map),
+            )
+            .0 || item_positions.contains(&(
+                position.current_map.clone(),
+                u32_from(build_x),
+                u32_from(build_y),
+            )) {
+                "Something is in the way."
+            } else {
+                build_position = Some((
+                    position.current_map.clone(),
+                    u32_from(build_x),
+                    u32_from(build_y),
+                ));
+                "You build a barricade."
+            };
+            send_message_to_player(
+                &player_details.player_name,
+                &player_details.client_addr,
+                player_details.currently_online,
+                message,
+                None,
+                sender,
+            );
+            break;
+        }
+    }
+
+    if let Some((map, x, y)) = build_position {
+        let mut player_query = <(&PlayerDetails, &mut Inventory)>::query();
+        for (player_details, inventory) in player_query.iter_mut(world) {
+            if player_details.player_name == *player_name {
+                inventory.gold -= BARRICADE_GOLD_COST;
+                inventory.update_available = true;
+                break;
+            }
+        }
+        info!("Player {player_name} built a barricade at ({x}, {y}) on {map}.");
+        pending_tile_changes.push(TileChange {
+            map,
+            x,
+            y,
+            character: BARRICADE_TILE_CHARACTER,
+        });
+    }
+}
@@ -15,8 +15,9 @@ pub(crate) fn set_player_velocity(world: &mut World, position_message: &Position
     let mut query = <(&mut PlayerDetails, &mut Position)>::query();
     query.par_for_each_mut(world, |(player_details, position)| {
         if player_details.player_name == position_message.player_name {
-            position.velocity_x = position_message.position.velocity_x;
-            position.velocity_y = position_message.position.velocity_y;
+            //players can only move one tile at a time, in any of the eight directions
+            position.velocity_x = position_message.position.velocity_x.clamp(-1, 1);
+            position.velocity_y = position_message.position.velocity_y.clamp(-1, 1);
         }
     });
 }
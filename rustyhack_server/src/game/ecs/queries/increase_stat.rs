@@ -1,4 +1,5 @@
 use crate::consts::BASE_HP_TABLE;
+use crate::game::abilities;
 use crate::network_messages::send_message_to_player;
 use crossbeam_channel::Sender;
 use laminar::Packet;
@@ -32,6 +33,7 @@ pub(crate) fn increase_stat(
                     stats.max_hp = (BASE_HP_TABLE[(stats.level - 1) as usize]
                         * (1.0 + (stats.con / 100.0)))
                         .round();
+                    abilities::update_max_resources(stats);
                     updated_stat = true;
                 }
                 _ => {}
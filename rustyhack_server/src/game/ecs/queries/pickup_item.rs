@@ -12,16 +12,25 @@ pub(crate) fn pickup_item(
     sender: &Sender<Packet>,
 ) {
     let mut item_option: Option<Item> = None;
+    let mut protected_corpse_option: Option<String> = None;
     let mut item_query = <(&mut ItemDetails, &Position, &Item)>::query();
 
     //confirm item exists at that position and get details
     for (requested_item_details, requested_item_position, requested_item) in
         item_query.iter_mut(world)
     {
-        if position_message.position.pos_x == requested_item_position.pos_x
+        if !requested_item_details.has_been_picked_up
+            && position_message.position.pos_x == requested_item_position.pos_x
             && position_message.position.pos_y == requested_item_position.pos_y
             && position_message.position.current_map == requested_item_position.current_map
         {
+            if let Item::Corpse(corpse) = requested_item
+                && !corpse.can_be_looted_by(&position_message.player_name)
+            {
+                //other items on the same tile can still be picked up
+                protected_corpse_option = Some(get_item_name(requested_item));
+                continue;
+            }
             item_option = Some(requested_item.clone());
             requested_item_details.has_been_picked_up = true;
             break;
@@ -33,6 +42,18 @@ pub(crate) fn pickup_item(
     player_query.par_for_each_mut(world, |(player_details, player_inventory)| {
         if player_details.player_name == position_message.player_name {
             match &item_option {
+                None if protected_corpse_option.is_some() => {
+                    send_message_to_player(
+                        &player_details.player_name,
+                        &player_details.client_addr,
+                        player_details.currently_online,
+                        &("You can't loot the ".to_string()
+                            + &protected_corpse_option.clone().unwrap_or_default()
+                            + " yet."),
+                        None,
+                        sender,
+                    );
+                }
                 None => {
                     debug!("No matching item found.");
                     send_message_to_player(
@@ -50,13 +71,22 @@ pub(crate) fn pickup_item(
                         player_details.player_name
                     );
                     let item_name = get_item_name(item);
-                    player_inventory.carried.push(item.clone());
+                    add_item_to_inventory(player_inventory, item);
                     player_inventory.update_available = true;
+                    let message = if let Item::Corpse(corpse) = item {
+                        "Looted ".to_string()
+                            + &corpse.items.len().to_string()
+                            + " items from the "
+                            + &item_name
+                            + "."
+                    } else {
+                        "Picked up ".to_string() + &item_name + "."
+                    };
                     send_message_to_player(
                         &player_details.player_name,
                         &player_details.client_addr,
                         player_details.currently_online,
-                        &("Picked up ".to_string() + &item_name + "."),
+                        &message,
                         None,
                         sender,
                     );
@@ -65,3 +95,16 @@ pub(crate) fn pickup_item(
         }
     });
 }
+
+//corpses are containers, so their contents are added rather than the corpse itself
+fn add_item_to_inventory(inventory: &mut Inventory, item: &Item) {
+    match item {
+        Item::Gold(amount) => inventory.gold += amount,
+        Item::Corpse(corpse) => {
+            for corpse_item in &corpse.items {
+                add_item_to_inventory(inventory, corpse_item);
+            }
+        }
+        _ => inventory.carried.push(item.clone()),
+    }
+}
@@ -1,29 +1,49 @@
+use crate::game::abilities;
+use crate::game::map::exits::AllMapExits;
 use crate::game::players;
 use bincode::{config, encode_to_vec};
 use crossbeam_channel::Sender;
 use laminar::Packet;
-use legion::{IntoQuery, World};
-use rustyhack_lib::ecs::components::{DisplayDetails, Inventory, PlayerDetails, Position, Stats};
+use legion::{Entity, IntoQuery, World};
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::background_map::character_map;
+use rustyhack_lib::background_map::tiles::Tile;
+use rustyhack_lib::ecs::ability::AllAbilities;
+use rustyhack_lib::ecs::components::{
+    AbilityTimers, DisplayDetails, ExploredMaps, Inventory, PlayerDetails, Position, Stats,
+    StatusEffects, TileChange,
+};
 use rustyhack_lib::ecs::player::Player;
 use rustyhack_lib::network::packets::ServerMessage;
+use std::collections::HashMap;
 use std::process;
 use uuid::Uuid;
 
 pub(crate) fn join_player(
     world: &mut World,
+    all_map_exits: &AllMapExits,
+    all_abilities: &AllAbilities,
+    all_maps: &AllMaps,
     name: &str,
     client_addr: String,
     sender: &Sender<Packet>,
 ) {
     let mut query = <(
+        Entity,
         &mut PlayerDetails,
         &mut DisplayDetails,
         &Position,
-        &Stats,
+        &mut Stats,
         &Inventory,
+        Option<&ExploredMaps>,
     )>::query();
     let mut should_create_new_player = true;
-    for (player_details, display_details, position, stats, inventory) in query.iter_mut(world) {
+    let mut existing_player_joined = false;
+    let mut entity_missing_explored_maps = None;
+    let mut joined_entity = None;
+    for (entity, player_details, display_details, position, stats, inventory, explored_maps) in
+        query.iter_mut(world)
+    {
         if player_details.player_name == name && !player_details.currently_online {
             player_details.currently_online = true;
             player_details.client_addr.clone_from(&client_addr);
@@ -33,6 +53,8 @@ pub(crate) fn join_player(
                 "Existing player \"{}\" logged in from: {}",
                 name, &client_addr
             );
+            //characters from older world backups won't have any mana or stamina yet
+            abilities::update_max_resources(stats);
             let player = Player {
                 player_details: player_details.clone(),
                 display_details: *display_details,
@@ -41,6 +63,14 @@ pub(crate) fn join_player(
                 inventory: inventory.clone(),
             };
             players::send_player_joined_response(&player, sender);
+            joined_entity = Some((*entity, player));
+            if let Some(explored_maps) = explored_maps {
+                players::send_explored_maps(explored_maps, &client_addr, sender);
+            } else {
+                //players from older world backups won't have explored any maps yet
+                entity_missing_explored_maps = Some(*entity);
+            }
+            existing_player_joined = true;
             should_create_new_player = false;
             break;
         } else if player_details.player_name == name && player_details.currently_online {
@@ -61,12 +91,88 @@ pub(crate) fn join_player(
             break;
         }
     }
+    if let Some(entity) = entity_missing_explored_maps
+        && let Some(mut entry) = world.entry(entity)
+    {
+        entry.add_component(ExploredMaps::default());
+    }
+    if let Some((entity, player)) = joined_entity
+        && let Some(mut entry) = world.entry(entity)
+    {
+        //players from older world backups won't have these yet
+        if entry.get_component::<AbilityTimers>().is_err() {
+            entry.add_component(AbilityTimers::default());
+        }
+        if let Ok(status_effects) = entry.get_component_mut::<StatusEffects>() {
+            status_effects.update_available = true;
+        } else {
+            entry.add_component(StatusEffects::default());
+        }
+        //explored tiles are otherwise only updated once the player moves
+        if let Ok(explored_maps) = entry.get_component_mut::<ExploredMaps>() {
+            players::explore_visible_tiles(
+                &player.player_details,
+                &player.position,
+                &player.inventory,
+                explored_maps,
+                all_maps,
+                sender,
+            );
+        }
+    }
+    if existing_player_joined {
+        send_map_details(world, all_map_exits, &client_addr, sender);
+        players::send_ability_definitions(all_abilities, &client_addr, sender);
+    }
     if should_create_new_player {
-        create_player(world, name, client_addr, sender);
+        send_map_details(world, all_map_exits, &client_addr, sender);
+        players::send_ability_definitions(all_abilities, &client_addr, sender);
+        create_player(world, all_maps, name, client_addr, sender);
+    }
+}
+
+fn send_map_details(
+    world: &World,
+    all_map_exits: &AllMapExits,
+    client_addr: &str,
+    sender: &Sender<Packet>,
+) {
+    send_all_tile_changes(world, client_addr, sender);
+    for (map, map_exits) in all_map_exits {
+        players::send_map_exits(map, map_exits, client_addr, sender);
+    }
+}
+
+fn send_all_tile_changes(world: &World, client_addr: &str, sender: &Sender<Packet>) {
+    //clients download the original maps, so need to be told about anything that's changed since
+    let mut all_tile_changes: HashMap<String, Vec<(u32, u32, Tile)>> = HashMap::new();
+    let mut query = <&TileChange>::query();
+    for tile_change in query.iter(world) {
+        all_tile_changes
+            .entry(tile_change.map.clone())
+            .or_default()
+            .push((
+                tile_change.x,
+                tile_change.y,
+                character_map::map_character_to_tile(
+                    tile_change.x,
+                    tile_change.y,
+                    tile_change.character,
+                ),
+            ));
+    }
+    for (map, tiles) in all_tile_changes {
+        players::send_tile_changes(&map, tiles, client_addr, sender);
     }
 }
 
-fn create_player(world: &mut World, name: &str, client_addr: String, sender: &Sender<Packet>) {
+fn create_player(
+    world: &mut World,
+    all_maps: &AllMaps,
+    name: &str,
+    client_addr: String,
+    sender: &Sender<Packet>,
+) {
     let player = Player {
         player_details: PlayerDetails {
             id: Uuid::new_v4(),
@@ -75,17 +181,30 @@ fn create_player(world: &mut World, name: &str, client_addr: String, sender: &Se
                 .expect("Something went wrong parsing player name."),
             client_addr,
             currently_online: true,
+            respawn_position: None,
         },
         ..Default::default()
     };
+    players::send_player_joined_response(&player, sender);
+    let mut explored_maps = ExploredMaps::default();
+    players::explore_visible_tiles(
+        &player.player_details,
+        &player.position,
+        &player.inventory,
+        &mut explored_maps,
+        all_maps,
+        sender,
+    );
 
     let player_entity = world.push((
-        player.player_details.clone(),
+        player.player_details,
         player.display_details,
-        player.position.clone(),
+        player.position,
         player.stats,
-        player.inventory.clone(),
+        player.inventory,
+        explored_maps,
+        AbilityTimers::default(),
+        StatusEffects::default(),
     ));
     info!("New player \"{name}\" created: {player_entity:?}");
-    players::send_player_joined_response(&player, sender);
 }
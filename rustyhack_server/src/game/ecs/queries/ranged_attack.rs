@@ -0,0 +1,23 @@
+use crate::game::combat::PendingRangedAttacks;
+use legion::{IntoQuery, World};
+use rustyhack_lib::ecs::components::{PlayerDetails, Stats};
+
+//the attack itself is checked and resolved with everything else on the next action tick
+pub(crate) fn queue_ranged_attack(
+    world: &World,
+    pending_ranged_attacks: &mut PendingRangedAttacks,
+    ranged_attack_details: &(String, u32, u32),
+) {
+    let (player_name, target_x, target_y) = ranged_attack_details;
+    let mut query = <(&PlayerDetails, &Stats)>::query();
+    for (player_details, stats) in query.iter(world) {
+        if player_details.player_name == *player_name
+            && player_details.currently_online
+            && stats.current_hp > 0.0
+        {
+            debug!("Queueing ranged attack from {player_name} at ({target_x}, {target_y}).");
+            pending_ranged_attacks.insert(player_details.id, (*target_x, *target_y));
+            break;
+        }
+    }
+}
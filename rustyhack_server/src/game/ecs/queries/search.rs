@@ -0,0 +1,85 @@
+use crate::consts::{SEARCH_BASE_CHANCE_PERCENTAGE, SEARCH_RADIUS};
+use crate::game::map::state;
+use crate::game::players;
+use crate::game::rng::GameRng;
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use laminar::Packet;
+use legion::{IntoQuery, World};
+use rand::RngExt;
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::background_map::tiles::Tile;
+use rustyhack_lib::ecs::components::{ExploredMaps, PlayerDetails, Position, Stats};
+use rustyhack_lib::network::packets::PositionMessage;
+use rustyhack_lib::utils::math::{i32_from, u32_from};
+
+pub(crate) fn search_for_traps(
+    world: &mut World,
+    all_maps: &AllMaps,
+    position_message: &PositionMessage,
+    rng: &mut GameRng,
+    sender: &Sender<Packet>,
+) {
+    let mut query = <(&PlayerDetails, &Position, &Stats, &mut ExploredMaps)>::query();
+    for (player_details, player_position, player_stats, explored_maps) in query.iter_mut(world) {
+        if player_details.player_name == position_message.player_name
+            && player_details.currently_online
+        {
+            //use the server side position rather than trusting the client
+            let current_map = state::get_current_map(all_maps, &player_position.current_map);
+            let discovered_traps = explored_maps
+                .discovered_traps
+                .entry(player_position.current_map.clone())
+                .or_default();
+            let search_chance = SEARCH_BASE_CHANCE_PERCENTAGE + player_stats.dex;
+            let mut found_traps = vec![];
+            for y in (i32_from(player_position.pos_y) - SEARCH_RADIUS)
+                ..=(i32_from(player_position.pos_y) + SEARCH_RADIUS)
+            {
+                for x in (i32_from(player_position.pos_x) - SEARCH_RADIUS)
+                    ..=(i32_from(player_position.pos_x) + SEARCH_RADIUS)
+                {
+                    if x < 0 || y < 0 || !current_map.is_within_boundary(u32_from(y), u32_from(x)) {
+                        continue;
+                    }
+                    let tile_position = (u32_from(x), u32_from(y));
+                    if *current_map.get_tile_at(tile_position.1, tile_position.0) == Tile::Trap
+                        && !discovered_traps.contains(&tile_position)
+                        && rng.random_range(0.0..100.0) < search_chance
+                    {
+                        discovered_traps.insert(tile_position);
+                        found_traps.push(tile_position);
+                    }
+                }
+            }
+
+            let message = if found_traps.is_empty() {
+                "You search the area but find nothing.".to_string()
+            } else {
+                "You found ".to_string() + &found_traps.len().to_string() + " hidden trap(s)!"
+            };
+            debug!(
+                "Player {} searched and found {} traps.",
+                player_details.player_name,
+                found_traps.len()
+            );
+            if !found_traps.is_empty() {
+                players::send_discovered_traps(
+                    &player_position.current_map,
+                    found_traps,
+                    &player_details.client_addr,
+                    sender,
+                );
+            }
+            send_message_to_player(
+                &player_details.player_name,
+                &player_details.client_addr,
+                player_details.currently_online,
+                &message,
+                None,
+                sender,
+            );
+            break;
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::consts::{PVP_TOGGLE_COOLDOWN_TICKS, SERVER_GAME_TICK};
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use crossterm::style::Color;
+use laminar::Packet;
+use legion::{IntoQuery, World};
+use rustyhack_lib::ecs::components::{PlayerDetails, Stats};
+
+pub(crate) fn toggle_pvp(world: &mut World, player_name: &str, sender: &Sender<Packet>) {
+    let mut query = <(&PlayerDetails, &mut Stats)>::query();
+    for (player_details, stats) in query.iter_mut(world) {
+        if player_details.player_name != player_name || !player_details.currently_online {
+            continue;
+        }
+        let (message, colour) = if stats.pvp_toggle_cooldown > 0 {
+            let seconds_left = u64::from(stats.pvp_toggle_cooldown) * SERVER_GAME_TICK.as_secs();
+            (
+                "You can't change your PvP flag for another ".to_string()
+                    + &seconds_left.to_string()
+                    + " seconds.",
+                None,
+            )
+        } else if stats.pvp_enabled {
+            stats.pvp_enabled = false;
+            (
+                "PvP disabled, other players can no longer attack you.".to_string(),
+                Some(Color::DarkGreen),
+            )
+        } else {
+            stats.pvp_enabled = true;
+            (
+                "PvP enabled, other players with PvP on can now attack you outside safe zones."
+                    .to_string(),
+                Some(Color::DarkRed),
+            )
+        };
+        if colour.is_some() {
+            debug!("Player {player_name} set PvP to {}.", stats.pvp_enabled);
+            stats.pvp_toggle_cooldown = PVP_TOGGLE_COOLDOWN_TICKS;
+            stats.update_available = true;
+        }
+        send_message_to_player(
+            &player_details.player_name,
+            &player_details.client_addr,
+            player_details.currently_online,
+            &message,
+            colour,
+            sender,
+        );
+        break;
+    }
+}
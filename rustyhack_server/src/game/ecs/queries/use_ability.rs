@@ -0,0 +1,25 @@
+use crate::game::abilities::PendingAbilities;
+use legion::{IntoQuery, World};
+use rustyhack_lib::ecs::components::{PlayerDetails, Stats};
+
+//the ability is checked and used with everything else on the next action tick
+pub(crate) fn queue_ability(
+    world: &World,
+    pending_abilities: &mut PendingAbilities,
+    ability_details: &(String, String, Option<(u32, u32)>),
+) {
+    let (player_name, ability_name, target) = ability_details;
+    let mut query = <(&PlayerDetails, &Stats)>::query();
+    for (player_details, stats) in query.iter(world) {
+        if player_details.player_name == *player_name
+            && player_details.currently_online
+            && stats.current_hp > 0.0
+        {
+            debug!("Queueing ability {ability_name} from {player_name} at {target:?}.");
+            pending_abilities
+                .requests
+                .insert(player_details.id, (ability_name.clone(), *target));
+            break;
+        }
+    }
+}
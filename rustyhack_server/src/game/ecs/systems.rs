@@ -1,10 +1,17 @@
+mod ability_systems;
+mod boss_systems;
 mod combat_systems;
+mod energy_systems;
+mod hazard_systems;
 mod map_state_systems;
 mod monster_systems;
 mod network_messages_systems;
 mod player_systems;
 mod position_systems;
+mod ranged_systems;
 mod regen_systems;
+mod status_effect_systems;
+mod terrain_systems;
 
 use legion::Schedule;
 
@@ -19,10 +26,15 @@ pub(crate) fn build_map_state_update_schedule() -> Schedule {
 
 pub(crate) fn build_player_update_schedule() -> Schedule {
     let schedule = Schedule::builder()
+        .add_system(status_effect_systems::prevent_stunned_movement_system())
+        .add_system(terrain_systems::check_for_terrain_attack_system())
         .add_system(position_systems::check_for_tile_collision_system())
         .add_system(combat_systems::check_for_combat_system())
-        .add_system(player_systems::resolve_player_deaths_system())
         .add_system(position_systems::update_entities_position_system())
+        .add_system(hazard_systems::trigger_tile_effects_system())
+        .add_system(player_systems::resolve_player_deaths_system())
+        .add_system(player_systems::update_explored_maps_system())
+        .add_system(terrain_systems::apply_tile_changes_system())
         .add_system(player_systems::clear_player_positions_resource_system())
         .add_system(player_systems::update_player_positions_resource_system())
         .build();
@@ -30,22 +42,55 @@ pub(crate) fn build_player_update_schedule() -> Schedule {
     schedule
 }
 
-pub(crate) fn build_server_tick_update_schedule() -> Schedule {
+pub(crate) fn build_action_tick_update_schedule() -> Schedule {
     let schedule = Schedule::builder()
+        .add_system(energy_systems::regenerate_action_energy_system())
         .add_system(monster_systems::update_monster_velocities_system())
+        .add_system(status_effect_systems::prevent_stunned_movement_system())
         .add_system(position_systems::check_for_tile_collision_system())
         .add_system(combat_systems::check_for_combat_system())
+        .add_system(ranged_systems::resolve_ranged_attacks_system())
+        .add_system(ranged_systems::clear_pending_ranged_attacks_system())
+        .add_system(ability_systems::resolve_abilities_system())
+        .add_system(ability_systems::clear_pending_abilities_system())
+        .add_system(boss_systems::queue_boss_area_attacks_system())
         .add_system(combat_systems::resolve_combat_system())
+        .add_system(combat_systems::process_combat_log_system())
+        .add_system(ability_systems::clear_pending_ability_area_attacks_system())
         .add_system(combat_systems::clear_combat_parties_system())
         .add_system(combat_systems::apply_combat_gains_system())
         .add_system(combat_systems::clear_combat_attacker_stats_system())
         .add_system(player_systems::level_up_system())
         .add_system(player_systems::resolve_player_deaths_system())
         .add_system(monster_systems::resolve_monster_deaths_system())
-        .add_system(monster_systems::spawn_monsters_system())
         .add_system(position_systems::update_entities_position_system())
+        .add_system(player_systems::update_explored_maps_system())
         .add_system(player_systems::clear_player_positions_resource_system())
         .add_system(player_systems::update_player_positions_resource_system())
+        .add_system(monster_systems::clear_monster_positions_resource_system())
+        .add_system(monster_systems::update_monster_positions_resource_system())
+        .add_system(boss_systems::send_announcements_system())
+        .build();
+    info!("Built action tick update system schedule.");
+    schedule
+}
+
+pub(crate) fn build_server_tick_update_schedule() -> Schedule {
+    let schedule = Schedule::builder()
+        .add_system(terrain_systems::resolve_terrain_attacks_system())
+        .add_system(terrain_systems::apply_tile_changes_system())
+        .add_system(hazard_systems::apply_hazard_damage_system())
+        .add_system(ability_systems::update_ability_cooldowns_system())
+        .add_system(player_systems::update_pvp_toggle_cooldowns_system())
+        .add_system(status_effect_systems::update_status_effects_system())
+        .add_system(player_systems::update_corpses_system())
+        .add_system(monster_systems::update_monster_threat_system())
+        .add_system(boss_systems::update_boss_phases_system())
+        .add_system(player_systems::resolve_player_deaths_system())
+        .add_system(monster_systems::resolve_monster_deaths_system())
+        .add_system(boss_systems::despawn_dismissed_summons_system())
+        .add_system(monster_systems::spawn_monsters_system())
+        .add_system(boss_systems::send_announcements_system())
         .build();
     info!("Built server tick update system schedule.");
     schedule
@@ -54,6 +99,7 @@ pub(crate) fn build_server_tick_update_schedule() -> Schedule {
 pub(crate) fn build_health_regen_schedule() -> Schedule {
     let schedule = Schedule::builder()
         .add_system(regen_systems::apply_health_regen_system())
+        .add_system(regen_systems::apply_mana_and_stamina_regen_system())
         .build();
     info!("Built health regen schedule.");
     schedule
@@ -64,6 +110,7 @@ pub(crate) fn send_network_messages_schedule() -> Schedule {
         .add_system(network_messages_systems::send_player_position_updates_system())
         .add_system(network_messages_systems::send_player_stats_updates_system())
         .add_system(network_messages_systems::send_player_inventory_updates_system())
+        .add_system(network_messages_systems::send_player_status_effects_updates_system())
         .build();
     info!("Built network messages schedule.");
     schedule
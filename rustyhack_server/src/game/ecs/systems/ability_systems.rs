@@ -0,0 +1,247 @@
+use super::combat_systems::{get_attacker, is_defender_lit};
+use super::ranged_systems::check_ranged_target;
+use crate::consts::DARKNESS_ACCURACY_PENALTY;
+use crate::game::abilities::{self, AbilityAreaAttack, PendingAbilities};
+use crate::game::action_energy::{self, ActionEnergy};
+use crate::game::combat::{CombatAttackerStats, CombatParties, Combatant};
+use crate::game::map::state::{self, AllMapStates};
+use crate::game::monsters::MonstersPositions;
+use crate::game::status_effects;
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use crossterm::style::Color;
+use laminar::Packet;
+use legion::system;
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::ability::{Ability, AbilityCost, AbilityEffect, AllAbilities};
+use rustyhack_lib::ecs::components::{
+    AbilityTimers, Inventory, PlayerDetails, Position, Stats, StatusEffects,
+};
+use rustyhack_lib::ecs::inventory::Weapon;
+
+#[allow(clippy::too_many_arguments)]
+#[system(for_each)]
+pub(super) fn resolve_abilities(
+    player_details: &PlayerDetails,
+    position: &Position,
+    stats: &mut Stats,
+    inventory: &Inventory,
+    ability_timers: &mut AbilityTimers,
+    status_effects: &mut StatusEffects,
+    #[resource] pending_abilities: &mut PendingAbilities,
+    #[resource] all_abilities: &AllAbilities,
+    #[resource] all_maps: &AllMaps,
+    #[resource] all_map_states: &mut AllMapStates,
+    #[resource] monsters_positions: &MonstersPositions,
+    #[resource] combat_parties: &mut CombatParties,
+    #[resource] combat_attacker_stats: &mut CombatAttackerStats,
+    #[resource] action_energy: &mut ActionEnergy,
+    #[resource] sender: &Sender<Packet>,
+) {
+    let Some((ability_name, target)) = pending_abilities.requests.get(&player_details.id).cloned()
+    else {
+        return;
+    };
+    if stats.current_hp <= 0.0 {
+        return;
+    }
+    let send_ability_message = |message: &str, colour: Option<Color>| {
+        send_message_to_player(
+            &player_details.player_name,
+            &player_details.client_addr,
+            player_details.currently_online,
+            message,
+            colour,
+            sender,
+        );
+    };
+    if status_effects::is_movement_prevented(status_effects) {
+        send_ability_message("You can't do that while stunned.", None);
+        return;
+    }
+    let ability = match get_usable_ability(all_abilities, &ability_name, stats, ability_timers) {
+        Ok(ability) => ability,
+        Err(message) => {
+            send_ability_message(&message, None);
+            return;
+        }
+    };
+
+    let ability_inventory = get_ability_inventory(ability, inventory);
+    let action_cost = action_energy::calculate_attack_cost(&ability_inventory);
+    if !action_energy::has_energy_for(action_energy, &player_details.id, action_cost) {
+        send_ability_message("You aren't ready to do that yet.", None);
+        return;
+    }
+
+    let attacker = get_attacker(Some(player_details), None);
+    let result = match &ability.effect {
+        AbilityEffect::Damage { range, .. } => {
+            let Some(target) = target else {
+                send_ability_message("You need a target for that.", None);
+                return;
+            };
+            let current_map = state::get_current_map(all_maps, &position.current_map);
+            check_ranged_target(
+                current_map,
+                state::get_current_map_states(all_map_states, &position.current_map),
+                monsters_positions,
+                None,
+                &Combatant {
+                    is_player: true,
+                    pvp_enabled: stats.pvp_enabled,
+                    tile: (position.pos_x, position.pos_y),
+                },
+                target,
+                *range,
+            )
+            .map(|(defender, attack_distance)| {
+                let mut ability_inventory = ability_inventory.clone();
+                if !is_defender_lit(current_map, target, position, inventory) {
+                    debug!("Player using ability on a target in darkness, reducing accuracy.");
+                    ability_inventory.equipped.weapon.accuracy -= DARKNESS_ACCURACY_PENALTY;
+                }
+                //using an ability takes the place of any other attack this tick
+                combat_parties.remove(&attacker);
+                combat_parties.insert(attacker.clone(), defender);
+                combat_attacker_stats
+                    .insert(attacker.id, (*stats, ability_inventory, attack_distance));
+            })
+        }
+        AbilityEffect::AreaDamage { radius, .. } => {
+            combat_parties.remove(&attacker);
+            combat_attacker_stats.insert(attacker.id, (*stats, ability_inventory.clone(), 1));
+            pending_abilities.area_attacks.push(AbilityAreaAttack {
+                attacker: attacker.clone(),
+                attacker_monster: None,
+                position: position.clone(),
+                radius: *radius,
+            });
+            Ok(())
+        }
+        AbilityEffect::Heal { .. } | AbilityEffect::Buff { .. } => {
+            apply_self_effect(ability, stats, status_effects);
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            debug!(
+                "Player {} used {}.",
+                player_details.player_name, ability.name
+            );
+            spend_ability(ability, stats, ability_timers);
+            action_energy::spend_energy(action_energy, &player_details.id, action_cost);
+            send_ability_message(
+                &("You use ".to_string() + &ability.name + "."),
+                Some(Color::Cyan),
+            );
+        }
+        Err(message) => send_ability_message(message, None),
+    }
+}
+
+fn get_usable_ability<'a>(
+    all_abilities: &'a AllAbilities,
+    ability_name: &str,
+    stats: &Stats,
+    ability_timers: &AbilityTimers,
+) -> Result<&'a Ability, String> {
+    let Some(ability) = all_abilities
+        .iter()
+        .find(|ability| ability.name == ability_name && ability.min_level <= stats.level)
+    else {
+        return Err("You don't know how to do that.".to_string());
+    };
+    if abilities::is_on_cooldown(ability_timers, &ability.name) {
+        return Err(ability.name.clone() + " isn't ready yet.");
+    }
+    if !abilities::can_afford(stats, ability.cost) {
+        let resource_name = match ability.cost {
+            AbilityCost::Mana(_) => "mana",
+            AbilityCost::Stamina(_) => "stamina",
+        };
+        return Err("You don't have enough ".to_string()
+            + resource_name
+            + " for "
+            + &ability.name
+            + ".");
+    }
+    Ok(ability)
+}
+
+fn apply_self_effect(ability: &Ability, stats: &mut Stats, status_effects: &mut StatusEffects) {
+    match &ability.effect {
+        AbilityEffect::Heal { amount } => {
+            stats.current_hp = (stats.current_hp + amount).min(stats.max_hp);
+        }
+        AbilityEffect::Buff { effect } => {
+            status_effects::apply_status_effect(status_effects, stats, effect);
+        }
+        AbilityEffect::Damage { .. } | AbilityEffect::AreaDamage { .. } => {}
+    }
+}
+
+fn spend_ability(ability: &Ability, stats: &mut Stats, ability_timers: &mut AbilityTimers) {
+    abilities::spend_cost(stats, ability.cost);
+    abilities::start_cooldown(ability_timers, ability);
+    if matches!(
+        ability.effect,
+        AbilityEffect::Damage { .. } | AbilityEffect::AreaDamage { .. }
+    ) {
+        stats.in_combat = true;
+    }
+    stats.update_available = true;
+}
+
+//abilities hit like a weapon made from the ability, so go through the normal combat formulas
+fn get_ability_inventory(ability: &Ability, inventory: &Inventory) -> Inventory {
+    let mut ability_inventory = inventory.clone();
+    let (damage_range, accuracy, range, on_hit, damage_type) = match &ability.effect {
+        AbilityEffect::Damage {
+            damage_range,
+            accuracy,
+            range,
+            on_hit,
+            damage_type,
+        } => (damage_range, accuracy, range, on_hit, damage_type),
+        AbilityEffect::AreaDamage {
+            damage_range,
+            accuracy,
+            radius,
+            on_hit,
+            damage_type,
+        } => (damage_range, accuracy, radius, on_hit, damage_type),
+        AbilityEffect::Heal { .. } | AbilityEffect::Buff { .. } => return ability_inventory,
+    };
+    ability_inventory.equipped.weapon = Weapon {
+        name: ability.name.clone(),
+        damage_range: damage_range.clone(),
+        accuracy: *accuracy,
+        range: *range,
+        //abilities take as long as an attack with the equipped weapon
+        speed: inventory.equipped.weapon.speed,
+        on_hit: on_hit.clone(),
+        damage_type: *damage_type,
+        ..Weapon::default()
+    };
+    ability_inventory
+}
+
+#[system]
+pub(super) fn clear_pending_abilities(#[resource] pending_abilities: &mut PendingAbilities) {
+    pending_abilities.requests.clear();
+}
+
+#[system]
+pub(super) fn clear_pending_ability_area_attacks(
+    #[resource] pending_abilities: &mut PendingAbilities,
+) {
+    pending_abilities.area_attacks.clear();
+}
+
+#[system(par_for_each)]
+pub(super) fn update_ability_cooldowns(ability_timers: &mut AbilityTimers) {
+    abilities::update_cooldowns(ability_timers);
+}
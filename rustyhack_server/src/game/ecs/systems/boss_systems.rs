@@ -0,0 +1,214 @@
+use super::combat_systems::get_attacker;
+use super::monster_systems::remove_monster;
+use crate::game::abilities::{AbilityAreaAttack, PendingAbilities};
+use crate::game::action_energy::ActionEnergy;
+use crate::game::combat::{CombatAttackerStats, CombatParties};
+use crate::game::map::state::{AllMapStates, EntityPositionMap};
+use crate::game::monsters::bosses::{
+    Announcement, AreaAttack, DismissedSummons, PendingAnnouncements, PendingAreaAttacks,
+};
+use crate::game::monsters::pathfinding::MonsterPaths;
+use crate::game::monsters::threat::ThreatTables;
+use crate::game::monsters::{MonstersPositions, bosses, movement, pathfinding, spawning};
+use crate::game::rng::GameRng;
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use crossterm::style::Color;
+use laminar::Packet;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::{Entity, Query, system};
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::components::{
+    BossState, Inventory, MonsterDetails, PlayerDetails, Position, Stats,
+};
+use rustyhack_lib::ecs::monster::{AllMonsterDefinitions, BossAbility};
+
+#[allow(clippy::trivially_copy_pass_by_ref, clippy::too_many_arguments)]
+#[system(for_each)]
+pub(super) fn update_boss_phases(
+    entity: &Entity,
+    monster: &MonsterDetails,
+    stats: &mut Stats,
+    position: &Position,
+    inventory: &Inventory,
+    boss_state_option: Option<&mut BossState>,
+    commands: &mut CommandBuffer,
+    #[resource] all_monster_definitions: &AllMonsterDefinitions,
+    #[resource] all_maps: &AllMaps,
+    #[resource] all_map_states: &AllMapStates,
+    #[resource] monsters_positions: &MonstersPositions,
+    #[resource] pending_area_attacks: &mut PendingAreaAttacks,
+    #[resource] pending_announcements: &mut PendingAnnouncements,
+    #[resource] dismissed_summons: &mut DismissedSummons,
+    #[resource] rng: &mut GameRng,
+) {
+    let Some(boss) = monster.boss.as_ref() else {
+        return;
+    };
+    let Some(boss_state) = boss_state_option else {
+        commands.add_component(*entity, BossState::default());
+        return;
+    };
+    if stats.current_hp <= 0.0 {
+        return;
+    }
+    if movement::check_if_outside_spawn_range(
+        &monster.spawn_position,
+        position,
+        monster.behaviour.leash_range,
+    ) {
+        debug!("Boss {} leashed, dismissing summons.", monster.monster_type);
+        bosses::dismiss_summons(boss_state, dismissed_summons);
+    }
+    for phase in bosses::get_new_phases(boss, boss_state, stats) {
+        debug!("Boss {} entering phase {phase}.", monster.monster_type);
+        boss_state.current_phase = phase;
+        bosses::apply_enrage(boss, phase, stats);
+        stats.update_available = true;
+        if !boss.phases[phase].announcement.is_empty() {
+            pending_announcements.push(Announcement {
+                map: Some(position.current_map.clone()),
+                message: boss.phases[phase].announcement.clone(),
+            });
+        }
+    }
+
+    if !bosses::is_ability_ready(monster, boss_state) {
+        return;
+    }
+    for ability in &boss.phases[boss_state.current_phase].abilities {
+        match ability {
+            BossAbility::AreaAttack { radius, damage } => {
+                debug!("Boss {} using area attack.", monster.monster_type);
+                pending_area_attacks.push(AreaAttack {
+                    monster: monster.clone(),
+                    position: position.clone(),
+                    radius: *radius,
+                    stats: *stats,
+                    inventory: bosses::get_area_attack_inventory(inventory, *damage),
+                });
+            }
+            BossAbility::Summon {
+                monster_type,
+                count,
+            } => {
+                debug!(
+                    "Boss {} summoning {count} {monster_type}.",
+                    monster.monster_type
+                );
+                let (Some(current_map), Some(map_state)) = (
+                    all_maps.get(&position.current_map),
+                    all_map_states.get(&position.current_map),
+                ) else {
+                    continue;
+                };
+                let summons_available =
+                    bosses::get_summons_available(boss_state, monsters_positions)
+                        .min(*count as usize);
+                let free_neighbours = pathfinding::get_free_neighbours(
+                    current_map,
+                    map_state,
+                    (position.pos_x, position.pos_y),
+                );
+                for (pos_x, pos_y) in free_neighbours.into_iter().take(summons_available) {
+                    if let Some(summon_id) = spawning::spawn_summoned_monster(
+                        all_monster_definitions,
+                        monster_type,
+                        monster.id,
+                        Position {
+                            pos_x,
+                            pos_y,
+                            ..position.clone()
+                        },
+                        rng,
+                        commands,
+                    ) {
+                        boss_state.summons.push(summon_id);
+                    }
+                }
+            }
+            BossAbility::Enrage { .. } => {}
+        }
+    }
+}
+
+//boss area attacks take the place of any other attack the boss makes this tick
+#[system]
+pub(super) fn queue_boss_area_attacks(
+    #[resource] pending_area_attacks: &mut PendingAreaAttacks,
+    #[resource] pending_abilities: &mut PendingAbilities,
+    #[resource] combat_parties: &mut CombatParties,
+    #[resource] combat_attacker_stats: &mut CombatAttackerStats,
+) {
+    for area_attack in pending_area_attacks.drain(..) {
+        let attacker = get_attacker(None, Some(&area_attack.monster));
+        combat_parties.remove(&attacker);
+        combat_attacker_stats.insert(attacker.id, (area_attack.stats, area_attack.inventory, 1));
+        pending_abilities.area_attacks.push(AbilityAreaAttack {
+            attacker,
+            attacker_monster: Some(area_attack.monster),
+            position: area_attack.position,
+            radius: area_attack.radius,
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+#[system]
+pub(super) fn despawn_dismissed_summons(
+    world: &mut SubWorld,
+    query: &mut Query<(Entity, &MonsterDetails, &Position)>,
+    commands: &mut CommandBuffer,
+    #[resource] dismissed_summons: &mut DismissedSummons,
+    #[resource] entity_position_map: &mut EntityPositionMap,
+    #[resource] monster_paths: &mut MonsterPaths,
+    #[resource] threat_tables: &mut ThreatTables,
+    #[resource] action_energy: &mut ActionEnergy,
+) {
+    if dismissed_summons.is_empty() {
+        return;
+    }
+    for (entity, monster, position) in query.iter(world) {
+        if dismissed_summons.contains(&monster.id) {
+            debug!("Despawning dismissed summon {}.", monster.id);
+            remove_monster(
+                *entity,
+                monster.id,
+                position,
+                commands,
+                entity_position_map,
+                (monster_paths, threat_tables, action_energy),
+            );
+        }
+    }
+    dismissed_summons.clear();
+}
+
+#[system]
+pub(super) fn send_announcements(
+    world: &mut SubWorld,
+    query: &mut Query<(&PlayerDetails, &Position)>,
+    #[resource] pending_announcements: &mut PendingAnnouncements,
+    #[resource] sender: &Sender<Packet>,
+) {
+    for announcement in pending_announcements.drain(..) {
+        info!("Announcing: {}", announcement.message);
+        for (player_details, position) in query.iter(world) {
+            if announcement
+                .map
+                .as_ref()
+                .is_none_or(|map| *map == position.current_map)
+            {
+                send_message_to_player(
+                    &player_details.player_name,
+                    &player_details.client_addr,
+                    player_details.currently_online,
+                    &announcement.message,
+                    Some(Color::Magenta),
+                    sender,
+                );
+            }
+        }
+    }
+}
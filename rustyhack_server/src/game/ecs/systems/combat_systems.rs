@@ -1,17 +1,35 @@
-use crate::consts::{GOLD_LOSS_ON_PVP_DEATH_PERCENTAGE, MONSTER_EXP_MULTIPLICATION_FACTOR};
-use crate::game::combat;
-use crate::game::combat::{Attacker, CombatAttackerStats, CombatParties, Defender};
-use crate::game::map::state;
-use crate::game::map::state::AllMapStates;
-use crate::network_messages::combat_updates;
+use crate::consts::{
+    DARKNESS_ACCURACY_PENALTY, GOLD_LOSS_ON_PVP_DEATH_PERCENTAGE,
+    MONSTER_EXP_MULTIPLICATION_FACTOR, MOVE_ENERGY_COST,
+};
+use crate::game::abilities::{AbilityAreaAttack, PendingAbilities};
+use crate::game::action_energy::ActionEnergy;
+use crate::game::combat::{Attacker, CombatAttackerStats, CombatParties, Combatant, Defender};
+use crate::game::combat_log::CombatLog;
+use crate::game::map::state::{AllMapStates, MapState};
+use crate::game::map::{state, tiles};
+use crate::game::monsters::MonstersPositions;
+use crate::game::monsters::bosses::{Announcement, PendingAnnouncements};
+use crate::game::monsters::pathfinding;
+use crate::game::monsters::threat::{self, ThreatTables};
+use crate::game::rng::GameRng;
+use crate::game::status_effects::{self, OnHitResult};
+use crate::game::{action_energy, combat};
+use crate::network_messages::{combat_updates, send_message_to_player};
 use crossbeam_channel::Sender;
+use crossterm::style::Color;
 use laminar::Packet;
 use legion::{maybe_changed, system};
+use rustyhack_lib::background_map::{AllMaps, BackgroundMap, field_of_view, lighting};
+use rustyhack_lib::consts::{PLAYER_FACTION, TORCH_TILE_LIGHT_RADIUS};
 use rustyhack_lib::ecs::components::{
     DisplayDetails, EntityType, Inventory, MonsterDetails, PlayerDetails, Position, Stats,
+    StatusEffects,
 };
+use rustyhack_lib::ecs::item::get_carried_light_radius;
 use rustyhack_lib::ecs::monster::Monster;
 use rustyhack_lib::ecs::player::Player;
+use rustyhack_lib::network::packets::CombatEvent;
 use rustyhack_lib::utils::math::{i32_from, u32_from};
 
 #[allow(clippy::too_many_arguments)]
@@ -24,101 +42,230 @@ pub(super) fn check_for_combat(
     display_details: &DisplayDetails,
     stats: &mut Stats,
     inventory: &Inventory,
+    #[resource] all_maps: &AllMaps,
     #[resource] all_map_states: &mut AllMapStates,
     #[resource] combat_parties: &mut CombatParties,
     #[resource] combat_attacker_stats: &mut CombatAttackerStats,
+    #[resource] action_energy: &mut ActionEnergy,
+    #[resource] monsters_positions: &MonstersPositions,
+    #[resource] sender: &Sender<Packet>,
 ) {
     debug!("Checking for possible combat after velocity updates.");
+    let potential_pos_x = u32_from(i32_from(position.pos_x) + position.velocity_x);
+    let potential_pos_y = u32_from(i32_from(position.pos_y) + position.velocity_y);
     if (position.velocity_x == 0 && position.velocity_y == 0) || stats.current_hp <= 0.0 {
         //either not moving, or already dead, no combat
         stats.in_combat = false;
+    } else if tiles::is_squeezing_between_walls(
+        state::get_current_map(all_maps, &position.current_map),
+        (position.pos_x, position.pos_y),
+        (potential_pos_x, potential_pos_y),
+    ) {
+        //can't reach diagonally between two walls, so no combat or movement
+        position.velocity_x = 0;
+        position.velocity_y = 0;
+        stats.in_combat = false;
     } else {
         let current_map_state =
             state::get_current_map_states(all_map_states, &position.current_map);
-        let potential_pos_x = u32_from(i32_from(position.pos_x) + position.velocity_x);
-        let potential_pos_y = u32_from(i32_from(position.pos_y) + position.velocity_y);
 
         let entity_collision_status =
             state::is_colliding_with_entity(potential_pos_x, potential_pos_y, current_map_state);
 
         let attacker = get_attacker(player_details_option, monster_details_option);
 
+        //attacking and moving both use up action energy, so do nothing until there's enough
+        let action_cost = if entity_collision_status.0 {
+            action_energy::calculate_attack_cost(inventory)
+        } else {
+            MOVE_ENERGY_COST
+        };
+        if !action_energy::has_energy_for(action_energy, &attacker.id, action_cost) {
+            debug!("Not enough action energy, setting velocity to 0.");
+            position.velocity_x = 0;
+            position.velocity_y = 0;
+            return;
+        }
+        action_energy::spend_energy(action_energy, &attacker.id, action_cost);
+
         if entity_collision_status.0 {
             //combat detected
-            if !attacker.is_player && !entity_collision_status.1.is_player {
-                debug!("Preventing possible monster combat.");
-                //monsters shouldn't attack other monsters, stop combat and movement
-                position.velocity_x = 0;
-                position.velocity_y = 0;
-                stats.in_combat = false;
-            } else {
+            if is_hostile(
+                monster_details_option,
+                &entity_collision_status.1,
+                monsters_positions,
+            ) && is_melee_combat_allowed(
+                state::get_current_map(all_maps, &position.current_map),
+                current_map_state,
+                &Combatant {
+                    is_player: attacker.is_player,
+                    pvp_enabled: stats.pvp_enabled,
+                    tile: (position.pos_x, position.pos_y),
+                },
+                &entity_collision_status.1,
+                (potential_pos_x, potential_pos_y),
+                player_details_option,
+                sender,
+            ) {
                 debug!(
                     "Combat detected, attacker is: {:?}, defender is: {:?}",
                     &attacker, &entity_collision_status.1
                 );
                 combat_parties.insert(attacker.clone(), entity_collision_status.1);
-                combat_attacker_stats.insert(attacker.id, (*stats, inventory.clone()));
+                let mut attacker_inventory = inventory.clone();
+                if attacker.is_player
+                    && !is_defender_lit(
+                        state::get_current_map(all_maps, &position.current_map),
+                        (potential_pos_x, potential_pos_y),
+                        position,
+                        inventory,
+                    )
+                {
+                    //monsters can see in the dark, but players fighting blind are less accurate
+                    debug!("Player attacking in darkness, reducing accuracy.");
+                    attacker_inventory.equipped.weapon.accuracy -= DARKNESS_ACCURACY_PENALTY;
+                }
+                //melee attacks are always against an adjacent tile
+                combat_attacker_stats.insert(attacker.id, (*stats, attacker_inventory, 1));
                 position.velocity_x = 0;
                 position.velocity_y = 0;
                 stats.in_combat = true;
+            } else {
+                debug!("Preventing combat between friendly entities or in a safe zone.");
+                //entities shouldn't attack anything on their own side, stop combat and movement
+                position.velocity_x = 0;
+                position.velocity_y = 0;
+                stats.in_combat = false;
             }
-        } else if attacker.is_player {
+        } else {
             //no combat detected - update map state for movement
-            let player = Player {
-                player_details: player_details_option.unwrap().clone(),
-                display_details: *display_details,
-                position: position.clone(),
-                stats: *stats,
-                inventory: inventory.clone(),
-            };
-            state::insert_entity_at(
-                all_map_states.get_mut(&position.current_map).unwrap(),
-                EntityType::Player(player.clone()),
-                potential_pos_x,
-                potential_pos_y,
+            let entity = get_entity_type(
+                player_details_option,
+                monster_details_option,
+                display_details,
+                position,
+                stats,
+                inventory,
             );
-            state::remove_entity_at(
-                all_map_states.get_mut(&position.current_map).unwrap(),
-                &EntityType::Player(player),
-                position.pos_x,
-                position.pos_y,
-            );
-            //player has moved, therefore no longer attacking
-            if combat_parties.contains_key(&attacker) {
-                combat_parties.remove(&attacker);
-            }
-            stats.in_combat = false;
-        } else if !attacker.is_player {
-            //no combat detected - update map state for movement
-            let monster = Monster {
-                monster_details: monster_details_option.unwrap().clone(),
-                display_details: *display_details,
-                position: position.clone(),
-                stats: *stats,
-                inventory: inventory.clone(),
-            };
+            let current_map_state = all_map_states.get_mut(&position.current_map).unwrap();
             state::insert_entity_at(
-                all_map_states.get_mut(&position.current_map).unwrap(),
-                EntityType::Monster(monster.clone()),
+                current_map_state,
+                entity.clone(),
                 potential_pos_x,
                 potential_pos_y,
             );
-            state::remove_entity_at(
-                all_map_states.get_mut(&position.current_map).unwrap(),
-                &EntityType::Monster(monster),
-                position.pos_x,
-                position.pos_y,
-            );
-            //monster has moved, therefore no longer attacking
-            if combat_parties.contains_key(&attacker) {
-                combat_parties.remove(&attacker);
-            }
+            state::remove_entity_at(current_map_state, &entity, position.pos_x, position.pos_y);
+            //entity has moved, therefore no longer attacking
+            combat_parties.remove(&attacker);
             stats.in_combat = false;
         }
     }
 }
 
-fn get_attacker(
+fn get_entity_type(
+    player_details_option: Option<&PlayerDetails>,
+    monster_details_option: Option<&MonsterDetails>,
+    display_details: &DisplayDetails,
+    position: &Position,
+    stats: &Stats,
+    inventory: &Inventory,
+) -> EntityType {
+    if let Some(player_details) = player_details_option {
+        EntityType::Player(Player {
+            player_details: player_details.clone(),
+            display_details: *display_details,
+            position: position.clone(),
+            stats: *stats,
+            inventory: inventory.clone(),
+        })
+    } else {
+        EntityType::Monster(Monster {
+            monster_details: monster_details_option.unwrap().clone(),
+            display_details: *display_details,
+            position: position.clone(),
+            stats: *stats,
+            inventory: inventory.clone(),
+        })
+    }
+}
+
+fn is_melee_combat_allowed(
+    current_map: &BackgroundMap,
+    current_map_state: &MapState,
+    attacker: &Combatant,
+    defender: &Defender,
+    defender_tile: (u32, u32),
+    player_details_option: Option<&PlayerDetails>,
+    sender: &Sender<Packet>,
+) -> bool {
+    let defender = Combatant {
+        is_player: defender.is_player,
+        pvp_enabled: state::is_player_pvp_enabled_at(
+            defender_tile.0,
+            defender_tile.1,
+            current_map_state,
+        ),
+        tile: defender_tile,
+    };
+    match combat::check_combat_allowed(current_map, attacker, &defender) {
+        Ok(()) => true,
+        Err(message) => {
+            if let Some(player_details) = player_details_option {
+                send_message_to_player(
+                    &player_details.player_name,
+                    &player_details.client_addr,
+                    player_details.currently_online,
+                    message,
+                    None,
+                    sender,
+                );
+            }
+            false
+        }
+    }
+}
+
+pub(super) fn is_defender_lit(
+    current_map: &BackgroundMap,
+    defender_tile: (u32, u32),
+    attacker_position: &Position,
+    attacker_inventory: &Inventory,
+) -> bool {
+    lighting::is_tile_lit(
+        current_map,
+        defender_tile,
+        (attacker_position.pos_x, attacker_position.pos_y),
+        get_carried_light_radius(&attacker_inventory.carried),
+        &lighting::get_light_sources_near(current_map, defender_tile, TORCH_TILE_LIGHT_RADIUS),
+    )
+}
+
+//monsters fight anything their faction is hostile to or anything they're fighting back against,
+//and nothing fights a monster on its own side, including pets fighting for players
+pub(super) fn is_hostile(
+    attacker_monster_option: Option<&MonsterDetails>,
+    defender: &Defender,
+    monsters_positions: &MonstersPositions,
+) -> bool {
+    let defender_faction = if defender.is_player {
+        PLAYER_FACTION
+    } else if let Some((_, faction)) = monsters_positions.get(&defender.id) {
+        faction
+    } else {
+        //monster has only just spawned, so isn't known about yet
+        return false;
+    };
+    match attacker_monster_option {
+        Some(monster) => {
+            !monster.faction.is_allied_with(defender_faction)
+                && (monster.faction.is_hostile_to(defender_faction)
+                    || monster.current_target == Some(defender.id))
+        }
+        None => defender.is_player || defender_faction != PLAYER_FACTION,
+    }
+}
+
+pub(super) fn get_attacker(
     player_details_option: Option<&PlayerDetails>,
     monster_details_option: Option<&MonsterDetails>,
 ) -> Attacker {
@@ -133,7 +280,7 @@ fn get_attacker(
     } else if let Some(monster_details) = monster_details_option {
         Attacker {
             id: monster_details.id,
-            name: monster_details.monster_type.clone(),
+            name: monster_details.display_name(),
             client_addr: String::new(),
             currently_online: true,
             is_player: false,
@@ -143,107 +290,278 @@ fn get_attacker(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 #[system(for_each)]
 pub(super) fn resolve_combat(
     defender_stats: &mut Stats,
-    monster_details_option: Option<&mut MonsterDetails>,
+    monster_details_option: Option<&MonsterDetails>,
     player_details_option: Option<&PlayerDetails>,
     defender_inventory: &mut Inventory,
+    defender_position: &Position,
+    mut defender_status_effects_option: Option<&mut StatusEffects>,
     #[resource] combat_parties: &CombatParties,
     #[resource] combat_attacker_stats: &mut CombatAttackerStats,
+    #[resource] pending_abilities: &PendingAbilities,
+    #[resource] all_maps: &AllMaps,
+    #[resource] monsters_positions: &MonstersPositions,
+    #[resource] threat_tables: &mut ThreatTables,
+    #[resource] combat_log: &mut CombatLog,
+    #[resource] rng: &mut GameRng,
     #[resource] sender: &Sender<Packet>,
 ) {
     // Skip combat if defender is already dead.
     // This is possible if multiple player updates are processed
     // before the server tick for monsters.
     if defender_stats.current_hp > 0.0 {
-        let mut defender_is_monster = false;
-        let mut monster_target = None;
-        let mut defender: Defender = Defender::default();
-        if let Some(player_details) = player_details_option {
-            //player is the defender
-            defender = Defender {
-                id: player_details.id,
-                name: player_details.player_name.clone(),
-                client_addr: player_details.client_addr.clone(),
-                currently_online: player_details.currently_online,
-                is_player: true,
-            };
-        } else if let Some(monster_details) = &monster_details_option {
-            //monster is the defender
-            defender = Defender {
-                id: monster_details.id,
-                name: monster_details.monster_type.clone(),
-                client_addr: String::new(),
-                currently_online: true,
-                is_player: false,
-            };
-            monster_target = monster_details.current_target;
-            defender_is_monster = true;
-        }
-        for (combat_parties_attacker, combat_parties_defender) in combat_parties {
-            if combat_parties_defender == &defender {
-                //combat detected
-                defender_stats.in_combat = true;
-                let attacker = combat_parties_attacker;
-                let (mut attacker_stats, mut attacker_inventory) =
-                    combat_attacker_stats.get(&attacker.id).unwrap().clone();
-                if attacker_stats.current_hp <= 0.0 {
-                    // Skip combat if attacker is already dead.
-                    // This is possible if combat is happening simultaneously.
-                    continue;
-                }
-                let damage = combat::resolve_combat(
-                    &attacker_stats,
-                    &attacker_inventory,
-                    defender_stats,
-                    defender_inventory,
-                )
-                .round();
-                apply_damage(defender_stats, damage);
-                if combat_attacker_stats.contains_key(&defender.id) {
-                    combat_attacker_stats
-                        .get_mut(&defender.id)
-                        .unwrap()
-                        .0
-                        .current_hp = defender_stats.current_hp;
-                }
-                let (exp_gain, gold_gain) = check_and_apply_gains(
+        let defender_is_monster = monster_details_option.is_some();
+        let defender = get_defender(player_details_option, monster_details_option);
+        let mut attackers: Vec<Attacker> = combat_parties
+            .iter()
+            .filter(|(_attacker, combat_parties_defender)| **combat_parties_defender == defender)
+            .map(|(attacker, _defender)| attacker.clone())
+            .chain(
+                pending_abilities
+                    .area_attacks
+                    .iter()
+                    .filter(|area_attack| {
+                        is_hit_by_area_attack(
+                            area_attack,
+                            combat_attacker_stats,
+                            (&defender, defender_stats, defender_position),
+                            all_maps,
+                            monsters_positions,
+                        )
+                    })
+                    .map(|area_attack| area_attack.attacker.clone()),
+            )
+            .collect();
+        //combat parties aren't ordered, so attackers take their rolls in id order for repeatable runs
+        attackers.sort_by_key(|attacker| attacker.id);
+        for attacker in &attackers {
+            //combat detected
+            defender_stats.in_combat = true;
+            let (mut attacker_stats, mut attacker_inventory, attack_distance) =
+                combat_attacker_stats.get(&attacker.id).unwrap().clone();
+            if attacker_stats.current_hp <= 0.0 {
+                // Skip combat if attacker is already dead.
+                // This is possible if combat is happening simultaneously.
+                continue;
+            }
+            let attack_outcome = combat::resolve_combat(
+                &attacker_stats,
+                &attacker_inventory,
+                defender_stats,
+                defender_inventory,
+                attack_distance,
+                rng,
+            );
+            let damage = attack_outcome.damage();
+            let defender_was_alive = defender_stats.current_hp > 0.0;
+            apply_damage(defender_stats, damage);
+            if damage > 0.0
+                && defender_stats.current_hp > 0.0
+                && let Some(defender_status_effects) = defender_status_effects_option.as_deref_mut()
+            {
+                apply_on_hit_status_effect(
                     attacker,
-                    defender_is_monster,
-                    combat_attacker_stats,
-                    &mut attacker_stats,
-                    &mut attacker_inventory,
-                    defender_stats,
-                    defender_inventory,
-                );
-                if defender_is_monster {
-                    //set current monster target as attacker
-                    monster_target = Some(attacker.id);
-                }
-                combat_updates::send_combat_updates_to_players(
                     &defender,
-                    attacker,
-                    damage,
-                    defender_stats.current_hp,
-                    exp_gain,
-                    gold_gain,
+                    &attacker_inventory,
+                    defender_stats,
+                    defender_status_effects,
+                    rng,
                     sender,
                 );
+            }
+            if combat_attacker_stats.contains_key(&defender.id) {
+                combat_attacker_stats
+                    .get_mut(&defender.id)
+                    .unwrap()
+                    .0
+                    .current_hp = defender_stats.current_hp;
+            }
+            let (exp_gain, gold_gain) = check_and_apply_gains(
+                attacker,
+                defender_is_monster,
+                combat_attacker_stats,
+                &mut attacker_stats,
+                &mut attacker_inventory,
+                defender_stats,
+                defender_inventory,
+            );
+            if defender_is_monster {
+                //attacking a monster draws its attention towards the attacker
+                threat::add_damage_threat(threat_tables, defender.id, attacker.id, damage);
+            }
+            let combat_event = CombatEvent {
+                attacker: attacker.name.clone(),
+                defender: defender.name.clone(),
+                outcome: attack_outcome,
+                killed: defender_was_alive && defender_stats.current_hp <= 0.0,
+            };
+            combat_updates::send_combat_updates_to_players(
+                &defender,
+                attacker,
+                &combat_event,
+                defender_stats.current_hp,
+                exp_gain,
+                gold_gain,
+                sender,
+            );
+            combat_log.record(
+                &defender_position.current_map,
+                combat_event,
+                monster_details_option.is_some_and(|monster| monster.boss.is_some()),
+            );
 
-                if let Some(_player_details) = player_details_option {
-                    //only set flag for players
-                    defender_stats.update_available = true;
-                }
+            if let Some(_player_details) = player_details_option {
+                //only set flag for players
+                defender_stats.update_available = true;
             }
         }
-        if defender_is_monster {
-            //set current_target to most recent attacker
-            monster_details_option.unwrap().current_target = monster_target;
+    }
+}
+
+#[system]
+pub(super) fn process_combat_log(
+    #[resource] combat_log: &mut CombatLog,
+    #[resource] pending_announcements: &mut PendingAnnouncements,
+) {
+    for logged_event in combat_log
+        .events
+        .iter()
+        .filter(|logged_event| logged_event.event.killed && logged_event.defender_is_boss)
+    {
+        pending_announcements.push(Announcement {
+            map: None,
+            message: "The ".to_string()
+                + &logged_event.event.defender
+                + " has been defeated by "
+                + &logged_event.event.attacker
+                + "!",
+        });
+    }
+    combat_log.write_events();
+}
+
+fn apply_on_hit_status_effect(
+    attacker: &Attacker,
+    defender: &Defender,
+    attacker_inventory: &Inventory,
+    defender_stats: &mut Stats,
+    defender_status_effects: &mut StatusEffects,
+    rng: &mut GameRng,
+    sender: &Sender<Packet>,
+) {
+    let Some(on_hit) = &attacker_inventory.equipped.weapon.on_hit else {
+        return;
+    };
+    let effect_name = &on_hit.effect.name;
+    let (defender_message, attacker_message) = match status_effects::roll_on_hit_effect(
+        on_hit,
+        defender_status_effects,
+        defender_stats,
+        rng,
+    ) {
+        OnHitResult::Missed => return,
+        OnHitResult::Resisted => (
+            "You resist ".to_string() + effect_name + " from " + &attacker.name + ".",
+            defender.name.clone() + " resists " + effect_name + ".",
+        ),
+        OnHitResult::Applied => {
+            debug!("{} is affected by {effect_name}.", defender.name);
+            defender_stats.update_available = true;
+            (
+                "You are affected by ".to_string() + effect_name + " from " + &attacker.name + "!",
+                defender.name.clone() + " is affected by " + effect_name + "!",
+            )
         }
+    };
+    if defender.is_player {
+        send_message_to_player(
+            &defender.name,
+            &defender.client_addr,
+            defender.currently_online,
+            &defender_message,
+            Some(Color::DarkYellow),
+            sender,
+        );
+    }
+    if attacker.is_player {
+        send_message_to_player(
+            &attacker.name,
+            &attacker.client_addr,
+            attacker.currently_online,
+            &attacker_message,
+            Some(Color::DarkYellow),
+            sender,
+        );
     }
 }
 
+fn get_defender(
+    player_details_option: Option<&PlayerDetails>,
+    monster_details_option: Option<&MonsterDetails>,
+) -> Defender {
+    if let Some(player_details) = player_details_option {
+        Defender {
+            id: player_details.id,
+            name: player_details.player_name.clone(),
+            client_addr: player_details.client_addr.clone(),
+            currently_online: player_details.currently_online,
+            is_player: true,
+        }
+    } else if let Some(monster_details) = monster_details_option {
+        Defender {
+            id: monster_details.id,
+            name: monster_details.display_name(),
+            client_addr: String::new(),
+            currently_online: true,
+            is_player: false,
+        }
+    } else {
+        Defender::default()
+    }
+}
+
+//area attacks hit everything hostile around the attacker that it can see
+fn is_hit_by_area_attack(
+    area_attack: &AbilityAreaAttack,
+    combat_attacker_stats: &CombatAttackerStats,
+    (defender, defender_stats, defender_position): (&Defender, &Stats, &Position),
+    all_maps: &AllMaps,
+    monsters_positions: &MonstersPositions,
+) -> bool {
+    let attacker_tile = (area_attack.position.pos_x, area_attack.position.pos_y);
+    let defender_tile = (defender_position.pos_x, defender_position.pos_y);
+    let current_map = state::get_current_map(all_maps, &defender_position.current_map);
+    let attacker_pvp_enabled = combat_attacker_stats
+        .get(&area_attack.attacker.id)
+        .is_some_and(|(attacker_stats, _, _)| attacker_stats.pvp_enabled);
+    area_attack.attacker.id != defender.id
+        && area_attack.position.current_map == defender_position.current_map
+        && pathfinding::get_distance(attacker_tile, defender_tile) <= area_attack.radius
+        && is_hostile(
+            area_attack.attacker_monster.as_ref(),
+            defender,
+            monsters_positions,
+        )
+        && combat::check_combat_allowed(
+            current_map,
+            &Combatant {
+                is_player: area_attack.attacker.is_player,
+                pvp_enabled: attacker_pvp_enabled,
+                tile: attacker_tile,
+            },
+            &Combatant {
+                is_player: defender.is_player,
+                pvp_enabled: defender_stats.pvp_enabled,
+                tile: defender_tile,
+            },
+        )
+        .is_ok()
+        && field_of_view::has_line_of_sight(current_map, attacker_tile, defender_tile)
+}
+
 #[system]
 pub(super) fn clear_combat_parties(#[resource] combat_parties: &mut CombatParties) {
     combat_parties.clear();
@@ -258,7 +576,8 @@ fn check_and_apply_gains(
     defender_stats: &Stats,
     defender_inventory: &mut Inventory,
 ) -> (u32, u32) {
-    if defender_stats.current_hp <= 0.0 {
+    //monsters killing each other gain nothing
+    if defender_stats.current_hp <= 0.0 && (attacker.is_player || !defender_is_monster) {
         //calculate xp to be gained
         let mut exp_gain = 0;
         if defender_is_monster {
@@ -279,7 +598,10 @@ fn check_and_apply_gains(
         if exp_gain > 0 || gold_gain > 0 {
             attacker_stats.update_available = true;
         }
-        combat_attacker_stats.insert(attacker.id, (*attacker_stats, attacker_inventory.clone()));
+        if let Some(attacker_combat_stats) = combat_attacker_stats.get_mut(&attacker.id) {
+            attacker_combat_stats.0 = *attacker_stats;
+            attacker_combat_stats.1 = attacker_inventory.clone();
+        }
 
         return (exp_gain, gold_gain);
     }
@@ -0,0 +1,29 @@
+use crate::game::action_energy;
+use crate::game::action_energy::ActionEnergy;
+use legion::system;
+use rustyhack_lib::ecs::components::{Inventory, MonsterDetails, PlayerDetails, Stats};
+
+#[system(for_each)]
+pub(super) fn regenerate_action_energy(
+    player_details_option: Option<&PlayerDetails>,
+    monster_details_option: Option<&MonsterDetails>,
+    stats: &Stats,
+    inventory: &Inventory,
+    #[resource] action_energy: &mut ActionEnergy,
+) {
+    let id = if let Some(player_details) = player_details_option
+        && player_details.currently_online
+    {
+        player_details.id
+    } else if let Some(monster_details) = monster_details_option {
+        monster_details.id
+    } else {
+        return;
+    };
+    if stats.current_hp <= 0.0 {
+        return;
+    }
+    let energy = action_energy.entry(id).or_default();
+    *energy = (*energy + action_energy::calculate_energy_gain(stats))
+        .min(action_energy::calculate_max_energy(inventory));
+}
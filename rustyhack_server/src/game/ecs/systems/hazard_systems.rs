@@ -0,0 +1,195 @@
+use crate::consts::{ACID_DAMAGE_PER_TICK, LAVA_DAMAGE_PER_TICK, TRAP_DAMAGE};
+use crate::game::map::state::{self, AllMapStates, MapState};
+use crate::game::players;
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use crossterm::style::Color;
+use laminar::Packet;
+use legion::{maybe_changed, system};
+use rustyhack_lib::background_map::tiles::Tile;
+use rustyhack_lib::background_map::{AllMaps, BackgroundMap};
+use rustyhack_lib::ecs::components::{ExploredMaps, PlayerDetails, Position, Stats};
+use rustyhack_lib::utils::math::{i32_from, u32_from};
+
+#[system(for_each)]
+#[filter(maybe_changed::<Position>())]
+pub(super) fn trigger_tile_effects(
+    player_details: &mut PlayerDetails,
+    position: &mut Position,
+    stats: &mut Stats,
+    explored_maps: &mut ExploredMaps,
+    #[resource] all_maps: &AllMaps,
+    #[resource] all_map_states: &mut AllMapStates,
+    #[resource] sender: &Sender<Packet>,
+) {
+    //update_available is only set when the player has just moved or been moved
+    if !position.update_available || stats.current_hp <= 0.0 {
+        return;
+    }
+    let current_map = state::get_current_map(all_maps, &position.current_map);
+    match current_map.get_tile_at(position.pos_y, position.pos_x) {
+        Tile::Trap => {
+            debug!(
+                "Player {} triggered a trap at ({}, {}).",
+                player_details.player_name, position.pos_x, position.pos_y
+            );
+            stats.current_hp -= TRAP_DAMAGE;
+            stats.update_available = true;
+            send_message_to_player(
+                &player_details.player_name,
+                &player_details.client_addr,
+                player_details.currently_online,
+                &("You triggered a trap and took ".to_string()
+                    + &TRAP_DAMAGE.to_string()
+                    + " damage!"),
+                Some(Color::DarkRed),
+                sender,
+            );
+            if explored_maps
+                .discovered_traps
+                .entry(position.current_map.clone())
+                .or_default()
+                .insert((position.pos_x, position.pos_y))
+            {
+                players::send_discovered_traps(
+                    &position.current_map,
+                    vec![(position.pos_x, position.pos_y)],
+                    &player_details.client_addr,
+                    sender,
+                );
+            }
+        }
+        Tile::TeleportPad(teleport_pad) => {
+            if let Some(destination) = teleport_pad.destination {
+                let Some((pos_x, pos_y)) = get_free_teleport_destination(
+                    current_map,
+                    state::get_current_map_states(all_map_states, &position.current_map),
+                    (destination.x, destination.y),
+                ) else {
+                    send_message_to_player(
+                        &player_details.player_name,
+                        &player_details.client_addr,
+                        player_details.currently_online,
+                        "The teleport pad flickers, something is blocking the other side.",
+                        Some(Color::Magenta),
+                        sender,
+                    );
+                    return;
+                };
+                debug!(
+                    "Player {} teleported to ({}, {}).",
+                    player_details.player_name, pos_x, pos_y
+                );
+                position.pos_x = pos_x;
+                position.pos_y = pos_y;
+                position.update_available = true;
+                send_message_to_player(
+                    &player_details.player_name,
+                    &player_details.client_addr,
+                    player_details.currently_online,
+                    "You step onto the teleport pad and are whisked away!",
+                    Some(Color::Magenta),
+                    sender,
+                );
+            }
+        }
+        Tile::BindStone => {
+            let bind_position = Position {
+                update_available: false,
+                velocity_x: 0,
+                velocity_y: 0,
+                ..position.clone()
+            };
+            if player_details.respawn_position.as_ref() != Some(&bind_position) {
+                debug!(
+                    "Player {} bound to stone at {:?}.",
+                    player_details.player_name, bind_position
+                );
+                player_details.respawn_position = Some(bind_position);
+                send_message_to_player(
+                    &player_details.player_name,
+                    &player_details.client_addr,
+                    player_details.currently_online,
+                    "You touch the bind stone, you will now respawn here.",
+                    Some(Color::Cyan),
+                    sender,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+//the destination if nothing is standing on it, otherwise the first free tile around it
+fn get_free_teleport_destination(
+    current_map: &BackgroundMap,
+    map_state: &MapState,
+    destination: (u32, u32),
+) -> Option<(u32, u32)> {
+    if !is_occupied(destination, map_state) {
+        return Some(destination);
+    }
+    let (dest_x, dest_y) = (i32_from(destination.0), i32_from(destination.1));
+    for offset_y in -1..=1 {
+        for offset_x in -1..=1 {
+            if dest_x + offset_x < 0 || dest_y + offset_y < 0 {
+                continue;
+            }
+            let tile_position = (u32_from(dest_x + offset_x), u32_from(dest_y + offset_y));
+            let tile = current_map.get_tile_at(tile_position.1, tile_position.0);
+            //only plain floor inside the map, so nobody lands on a hazard or another pad
+            if *tile == Tile::EmptySpace
+                && current_map.is_within_boundary(tile_position.1, tile_position.0)
+                && !is_occupied(tile_position, map_state)
+            {
+                return Some(tile_position);
+            }
+        }
+    }
+    None
+}
+
+fn is_occupied((x, y): (u32, u32), map_state: &MapState) -> bool {
+    state::is_colliding_with_entity(x, y, map_state).0
+}
+
+#[system(for_each)]
+pub(super) fn apply_hazard_damage(
+    position: &Position,
+    stats: &mut Stats,
+    player_details_option: Option<&PlayerDetails>,
+    #[resource] all_maps: &AllMaps,
+    #[resource] sender: &Sender<Packet>,
+) {
+    if stats.current_hp <= 0.0 {
+        return;
+    }
+    if let Some(player_details) = player_details_option
+        && !player_details.currently_online
+    {
+        return;
+    }
+    let current_map = state::get_current_map(all_maps, &position.current_map);
+    let (damage, hazard_name) = match current_map.get_tile_at(position.pos_y, position.pos_x) {
+        Tile::Lava => (LAVA_DAMAGE_PER_TICK, "lava"),
+        Tile::Acid => (ACID_DAMAGE_PER_TICK, "acid"),
+        _ => return,
+    };
+    debug!("Entity standing in {hazard_name}, applying {damage} damage.");
+    stats.current_hp -= damage;
+    if let Some(player_details) = player_details_option {
+        stats.update_available = true;
+        send_message_to_player(
+            &player_details.player_name,
+            &player_details.client_addr,
+            player_details.currently_online,
+            &("The ".to_string()
+                + hazard_name
+                + " burns you for "
+                + &damage.to_string()
+                + " damage."),
+            Some(Color::DarkRed),
+            sender,
+        );
+    }
+}
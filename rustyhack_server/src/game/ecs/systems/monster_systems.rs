@@ -1,20 +1,32 @@
-use crate::game::map::spawns::{AllSpawnCounts, AllSpawnsMap};
-use crate::game::map::state::EntityPositionMap;
-use crate::game::monsters::{movement, spawning};
+use crate::consts::MOVE_ENERGY_COST;
+use crate::game::action_energy;
+use crate::game::action_energy::ActionEnergy;
+use crate::game::combat::PendingRangedAttacks;
+use crate::game::map::spawns::{AllSpawnCounts, AllSpawnTimers, AllSpawnsMap};
+use crate::game::map::state::{AllMapStates, EntityPositionMap};
+use crate::game::monsters::bosses::DismissedSummons;
+use crate::game::monsters::loot;
+use crate::game::monsters::loot::AllLootTables;
+use crate::game::monsters::pathfinding::MonsterPaths;
+use crate::game::monsters::threat::ThreatTables;
+use crate::game::monsters::{MonstersPositions, behaviour, bosses, spawning};
 use crate::game::players::PlayersPositions;
+use crate::game::rng::{self, GameRng};
+use crossterm::style::Color;
 use legion::systems::CommandBuffer;
 use legion::world::SubWorld;
 use legion::{Entity, Query, maybe_changed, system};
+use rustyhack_lib::background_map::AllMaps;
 use rustyhack_lib::consts::{DEAD_MAP, DEFAULT_ITEM_COLOUR, DEFAULT_ITEM_ICON};
 use rustyhack_lib::ecs::components::{
-    Dead, DisplayDetails, Inventory, ItemDetails, MonsterDetails, Position, Stats,
+    BossState, Dead, DisplayDetails, Inventory, ItemDetails, MonsterDetails, Position, Stats,
 };
 use rustyhack_lib::ecs::item::Item;
 use rustyhack_lib::ecs::monster::AllMonsterDefinitions;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
-#[allow(clippy::trivially_copy_pass_by_ref)]
+#[allow(clippy::trivially_copy_pass_by_ref, clippy::too_many_arguments)]
 #[system(for_each)]
 #[filter(maybe_changed::<Stats>())]
 pub(super) fn resolve_monster_deaths(
@@ -23,26 +35,45 @@ pub(super) fn resolve_monster_deaths(
     stats: &Stats,
     position: &Position,
     inventory: &Inventory,
+    boss_state_option: Option<&mut BossState>,
     commands: &mut CommandBuffer,
     #[resource] entity_position_map: &mut EntityPositionMap,
+    #[resource] monster_paths: &mut MonsterPaths,
+    #[resource] threat_tables: &mut ThreatTables,
+    #[resource] action_energy: &mut ActionEnergy,
+    #[resource] all_loot_tables: &AllLootTables,
+    #[resource] dismissed_summons: &mut DismissedSummons,
+    #[resource] rng: &mut GameRng,
 ) {
     if stats.current_hp <= 0.0 {
         debug!(
             "Monster {} {:?} {} died.",
             monster.id, entity, monster.monster_type
         );
-        //drop inventory items
+        //drop inventory items, and roll for loot unless summoned
         let mut items_vec: Vec<(ItemDetails, DisplayDetails, Position, Item)> = vec![];
         debug!("Monster inventory was: {inventory:?}");
-        for item in &inventory.carried {
+        let mut dropped_items: Vec<(Item, Color)> = inventory
+            .carried
+            .iter()
+            .map(|item| (item.clone(), DEFAULT_ITEM_COLOUR))
+            .collect();
+        if monster.summoned_by.is_none() {
+            dropped_items.extend(
+                loot::roll_loot(all_loot_tables, &monster.monster_type, rng)
+                    .into_iter()
+                    .map(|(item, rarity)| (item, rarity.colour())),
+            );
+        }
+        for (item, colour) in dropped_items {
             items_vec.push((
                 ItemDetails {
-                    id: Uuid::new_v4(),
+                    id: rng::new_uuid(rng),
                     has_been_picked_up: false,
                 },
                 DisplayDetails {
                     icon: DEFAULT_ITEM_ICON,
-                    colour: DEFAULT_ITEM_COLOUR,
+                    colour,
                     visible: true,
                     collidable: false,
                 },
@@ -54,90 +85,173 @@ pub(super) fn resolve_monster_deaths(
                     velocity_x: 0,
                     velocity_y: 0,
                 },
-                item.clone(),
+                item,
             ));
         }
         //add dropped item entities to world
         debug!("Items being added to world are: {items_vec:?}");
         commands.extend(items_vec);
-        let dead_position = Position {
-            current_map: position.current_map.clone() + DEAD_MAP,
-            ..Dead::dead()
-        };
-        entity_position_map.insert(
+        if let Some(boss_state) = boss_state_option {
+            bosses::dismiss_summons(boss_state, dismissed_summons);
+        }
+        remove_monster(
+            *entity,
             monster.id,
-            (
-                dead_position,
-                DisplayDetails::dead(),
-                "dead_monster".to_string(),
-            ),
+            position,
+            commands,
+            entity_position_map,
+            (monster_paths, threat_tables, action_energy),
         );
-        //remove monster from world
-        commands.remove(*entity);
     }
 }
 
-#[system(par_for_each)]
+pub(super) fn remove_monster(
+    entity: Entity,
+    monster_id: Uuid,
+    position: &Position,
+    commands: &mut CommandBuffer,
+    entity_position_map: &mut EntityPositionMap,
+    (monster_paths, threat_tables, action_energy): (
+        &mut MonsterPaths,
+        &mut ThreatTables,
+        &mut ActionEnergy,
+    ),
+) {
+    let dead_position = Position {
+        current_map: position.current_map.clone() + DEAD_MAP,
+        ..Dead::dead()
+    };
+    entity_position_map.insert(
+        monster_id,
+        (
+            dead_position,
+            DisplayDetails::dead(),
+            "dead_monster".to_string(),
+        ),
+    );
+    monster_paths.remove(&monster_id);
+    threat_tables.remove(&monster_id);
+    action_energy.remove(&monster_id);
+    //remove monster from world
+    commands.remove(entity);
+}
+
+#[allow(clippy::too_many_arguments)]
+#[system(for_each)]
 pub(super) fn update_monster_velocities(
     monster: &mut MonsterDetails,
     position: &mut Position,
+    stats: &Stats,
+    inventory: &Inventory,
     #[resource] players_positions: &PlayersPositions,
+    #[resource] monsters_positions: &MonstersPositions,
+    #[resource] all_maps: &AllMaps,
+    #[resource] all_map_states: &AllMapStates,
+    #[resource] monster_paths: &mut MonsterPaths,
+    #[resource] threat_tables: &mut ThreatTables,
+    #[resource] action_energy: &ActionEnergy,
+    #[resource] pending_ranged_attacks: &mut PendingRangedAttacks,
+    #[resource] rng: &mut GameRng,
 ) {
+    //monsters only decide where to go once they have the energy to act on it
+    if !action_energy::has_energy_for(action_energy, &monster.id, MOVE_ENERGY_COST) {
+        return;
+    }
     debug!("Updating monster velocities - checking for movement to player positions");
-    let mut moving_towards_existing_target = false;
+    behaviour::update_monster_velocity(
+        monster,
+        position,
+        stats,
+        inventory,
+        players_positions,
+        monsters_positions,
+        all_maps,
+        all_map_states,
+        monster_paths,
+        threat_tables,
+        pending_ranged_attacks,
+        rng,
+    );
+}
 
-    //get nearby players and whether monster is currently outside its spawn range
-    let nearby_players = movement::get_all_players_nearby(players_positions, position);
-    let outside_spawn_range =
-        movement::check_if_outside_spawn_range(&monster.spawn_position, position);
+#[system]
+pub(super) fn clear_monster_positions_resource(
+    #[resource] monsters_positions: &mut MonstersPositions,
+) {
+    monsters_positions.clear();
+}
 
-    //check if current target within range and move towards it
-    if let Some(target) = monster.current_target
-        && nearby_players.contains_key(&target)
-    {
-        debug!("Monster moving towards existing target.");
-        movement::move_towards_target(position, nearby_players.get(&target).unwrap());
-        moving_towards_existing_target = true;
+#[system(for_each)]
+pub(super) fn update_monster_positions_resource(
+    monster_details: &MonsterDetails,
+    position: &Position,
+    stats: &Stats,
+    #[resource] monsters_positions: &mut MonstersPositions,
+) {
+    if stats.current_hp > 0.0 {
+        monsters_positions.insert(
+            monster_details.id,
+            (position.clone(), monster_details.faction.name.clone()),
+        );
     }
+}
 
-    //else either return to spawn, pick a new target, or move randomly
-    if outside_spawn_range && !moving_towards_existing_target {
-        debug!("Monster returning to spawn location.");
-        monster.current_target = None;
-        movement::move_towards_target(position, &monster.spawn_position);
-    } else if !outside_spawn_range && !moving_towards_existing_target && !nearby_players.is_empty()
-    {
-        debug!("Monster moving towards new target.");
-        let nearest_target = movement::get_nearest_target(&nearby_players, position);
-        monster.current_target = Some(nearest_target);
-        movement::move_towards_target(position, nearby_players.get(&nearest_target).unwrap());
-    } else if !outside_spawn_range && !moving_towards_existing_target && nearby_players.is_empty() {
-        debug!("Monster moving randomly.");
-        movement::move_randomly(position);
-    }
+#[system(for_each)]
+pub(super) fn update_monster_threat(
+    monster: &MonsterDetails,
+    position: &Position,
+    #[resource] players_positions: &PlayersPositions,
+    #[resource] monsters_positions: &MonstersPositions,
+    #[resource] threat_tables: &mut ThreatTables,
+) {
+    behaviour::update_monster_threat(
+        monster,
+        position,
+        players_positions,
+        monsters_positions,
+        threat_tables,
+    );
 }
 
 #[system]
+#[allow(clippy::too_many_arguments)]
 pub(super) fn spawn_monsters(
     world: &mut SubWorld,
     query: &mut Query<(&MonsterDetails, &Position)>,
     commands: &mut CommandBuffer,
     #[resource] all_spawns_map: &AllSpawnsMap,
-    #[resource] default_spawn_counts: &AllSpawnCounts,
+    #[resource] spawn_timers: &mut AllSpawnTimers,
     #[resource] all_monster_definitions: &AllMonsterDefinitions,
+    #[resource] all_maps: &AllMaps,
+    #[resource] players_positions: &PlayersPositions,
+    #[resource] rng: &mut GameRng,
 ) {
     debug!("Checking whether replacement monsters need to spawn.");
     let mut current_monsters_count: AllSpawnCounts = HashMap::new();
+    let mut blocked_spawn_tiles: HashMap<String, HashSet<(u32, u32)>> = HashMap::new();
     for (monster, position) in query.iter(world) {
-        current_monsters_count =
-            spawning::count_alive_monsters(current_monsters_count, monster, position);
+        blocked_spawn_tiles
+            .entry(position.current_map.clone())
+            .or_default()
+            .insert((position.pos_x, position.pos_y));
+        if monster.summoned_by.is_none() {
+            current_monsters_count = spawning::count_alive_monsters(
+                current_monsters_count,
+                monster,
+                position,
+                all_spawns_map,
+            );
+        }
     }
-    let monsters_needing_respawn: AllSpawnCounts =
-        spawning::count_monsters_needing_respawn(&current_monsters_count, default_spawn_counts);
+    spawning::add_tiles_seen_by_players(&mut blocked_spawn_tiles, players_positions, all_maps);
     spawning::respawn_monsters(
-        &monsters_needing_respawn,
+        &current_monsters_count,
         all_monster_definitions,
         all_spawns_map,
+        all_maps,
+        &mut blocked_spawn_tiles,
+        spawn_timers,
+        rng,
         commands,
     );
 }
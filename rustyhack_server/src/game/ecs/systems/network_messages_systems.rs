@@ -4,7 +4,9 @@ use crossbeam_channel::Sender;
 use laminar::Packet;
 use legion::{maybe_changed, system};
 use rustyhack_lib::consts::DEAD_MAP;
-use rustyhack_lib::ecs::components::{DisplayDetails, Inventory, PlayerDetails, Position, Stats};
+use rustyhack_lib::ecs::components::{
+    DisplayDetails, Inventory, PlayerDetails, Position, Stats, StatusEffects,
+};
 use rustyhack_lib::network::packets::ServerMessage;
 use std::process;
 use uuid::Uuid;
@@ -111,6 +113,41 @@ pub(super) fn send_player_inventory_updates(
     }
 }
 
+#[system(par_for_each)]
+#[filter(maybe_changed::<StatusEffects>())]
+pub(super) fn send_player_status_effects_updates(
+    player_details: &PlayerDetails,
+    status_effects: &mut StatusEffects,
+    #[resource] sender: &Sender<Packet>,
+) {
+    if status_effects.update_available && player_details.currently_online {
+        debug!(
+            "Sending player status effects update for: {}",
+            &player_details.player_name
+        );
+        let response = encode_to_vec(
+            ServerMessage::UpdateStatusEffects(status_effects.clone()),
+            config::standard(),
+        )
+        .unwrap_or_else(|err| {
+            error!(
+                "Failed to encode player status effects: {:?}, error: {}",
+                &status_effects, err
+            );
+            process::exit(1);
+        });
+        rustyhack_lib::network::send_packet(
+            Packet::reliable_ordered(
+                player_details.client_addr.parse().unwrap(),
+                response,
+                Some(30),
+            ),
+            sender,
+        );
+        status_effects.update_available = false;
+    }
+}
+
 #[system(par_for_each)]
 pub(super) fn broadcast_entity_updates(
     player_details: &PlayerDetails,
@@ -1,30 +1,67 @@
-use crate::consts::{BASE_HP_TABLE, CUMULATIVE_EXP_TABLE, EXP_LOSS_ON_DEATH_PERCENTAGE};
-use crate::game::players::PlayersPositions;
+use crate::consts::{
+    BASE_HP_TABLE, CORPSE_DECAY_TICKS, CORPSE_PROTECTION_TICKS, CUMULATIVE_EXP_TABLE,
+    EXP_LOSS_ON_DEATH_PERCENTAGE,
+};
+use crate::game::abilities;
+use crate::game::players;
+use crate::game::players::{DeathPenaltyMode, PlayersPositions};
+use crate::game::rng::{self, GameRng};
+use crate::game::status_effects;
 use crate::network_messages::send_message_to_player;
 use crossbeam_channel::Sender;
 use crossterm::style::Color;
 use laminar::Packet;
+use legion::systems::CommandBuffer;
 use legion::{maybe_changed, system};
-use rustyhack_lib::ecs::components::{PlayerDetails, Position, Stats};
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::consts::{CORPSE_COLOUR, CORPSE_ICON};
+use rustyhack_lib::ecs::ability::AllAbilities;
+use rustyhack_lib::ecs::components::{
+    DisplayDetails, ExploredMaps, Inventory, ItemDetails, PlayerDetails, Position, Stats,
+    StatusEffects,
+};
+use rustyhack_lib::ecs::item::{Corpse, Item};
 
-#[system(par_for_each)]
+#[system(for_each)]
 #[filter(maybe_changed::<Stats>())]
+#[allow(clippy::trivially_copy_pass_by_ref, clippy::too_many_arguments)]
 pub(super) fn resolve_player_deaths(
     player_details: &PlayerDetails,
     position: &mut Position,
     stats: &mut Stats,
+    inventory: &mut Inventory,
+    status_effects_option: Option<&mut StatusEffects>,
+    commands: &mut CommandBuffer,
+    #[resource] death_penalty_mode: &DeathPenaltyMode,
+    #[resource] rng: &mut GameRng,
     #[resource] sender: &Sender<Packet>,
 ) {
     if stats.current_hp <= 0.0 {
+        if let Some(status_effects) = status_effects_option {
+            status_effects::clear_status_effects(status_effects, stats);
+        }
+        if *death_penalty_mode == DeathPenaltyMode::Corpse && !inventory.carried.is_empty() {
+            drop_corpse(player_details, position, inventory, commands, rng);
+            send_message_to_player(
+                &player_details.player_name,
+                &player_details.client_addr,
+                player_details.currently_online,
+                "Your carried items were left behind in your corpse.",
+                Some(Color::DarkYellow),
+                sender,
+            );
+        }
         let mut exp_loss = 0;
         if stats.exp > 100 {
             exp_loss = (stats.exp * EXP_LOSS_ON_DEATH_PERCENTAGE) / 100;
             stats.exp -= exp_loss;
         }
         stats.current_hp = stats.max_hp;
+        stats.current_mana = stats.max_mana;
+        stats.current_stamina = stats.max_stamina;
         stats.in_combat = false;
         stats.update_available = true;
-        *position = Position::default();
+        *position = player_details.respawn_position.clone().unwrap_or_default();
         position.update_available = true;
         if exp_loss > 0 {
             send_message_to_player(
@@ -47,6 +84,63 @@ pub(super) fn resolve_player_deaths(
     }
 }
 
+#[system(par_for_each)]
+pub(super) fn update_pvp_toggle_cooldowns(stats: &mut Stats) {
+    stats.pvp_toggle_cooldown = stats.pvp_toggle_cooldown.saturating_sub(1);
+}
+
+fn drop_corpse(
+    player_details: &PlayerDetails,
+    position: &Position,
+    inventory: &mut Inventory,
+    commands: &mut CommandBuffer,
+    rng: &mut GameRng,
+) {
+    debug!(
+        "Player {} died, dropping corpse with items: {:?}",
+        player_details.player_name, inventory.carried
+    );
+    let corpse = Corpse {
+        owner_name: player_details.player_name.clone(),
+        items: inventory.carried.drain(..).collect(),
+        protected_ticks: CORPSE_PROTECTION_TICKS,
+        age_ticks: 0,
+    };
+    inventory.update_available = true;
+    commands.push((
+        ItemDetails {
+            id: rng::new_uuid(rng),
+            has_been_picked_up: false,
+        },
+        DisplayDetails {
+            icon: CORPSE_ICON,
+            colour: CORPSE_COLOUR,
+            visible: true,
+            collidable: false,
+        },
+        Position {
+            update_available: true,
+            velocity_x: 0,
+            velocity_y: 0,
+            ..position.clone()
+        },
+        Item::Corpse(corpse),
+    ));
+}
+
+#[system(for_each)]
+pub(super) fn update_corpses(item_details: &mut ItemDetails, item: &mut Item) {
+    if let Item::Corpse(corpse) = item {
+        corpse.protected_ticks = corpse.protected_ticks.saturating_sub(1);
+        corpse.age_ticks += 1;
+        if corpse.age_ticks >= CORPSE_DECAY_TICKS {
+            //rotted corpses are removed along with their contents, the same as picked up items
+            debug!("Corpse of {} has rotted away.", corpse.owner_name);
+            item_details.has_been_picked_up = true;
+        }
+    }
+}
+
 #[system]
 pub(super) fn clear_player_positions_resource(
     #[resource] players_positions: &mut PlayersPositions,
@@ -65,11 +159,32 @@ pub(super) fn update_player_positions_resource(
     }
 }
 
+#[system(par_for_each)]
+#[filter(maybe_changed::<Position>())]
+pub(super) fn update_explored_maps(
+    player_details: &PlayerDetails,
+    position: &Position,
+    inventory: &Inventory,
+    explored_maps: &mut ExploredMaps,
+    #[resource] all_maps: &AllMaps,
+    #[resource] sender: &Sender<Packet>,
+) {
+    players::explore_visible_tiles(
+        player_details,
+        position,
+        inventory,
+        explored_maps,
+        all_maps,
+        sender,
+    );
+}
+
 #[system(par_for_each)]
 #[filter(maybe_changed::<Stats>())]
 pub(super) fn level_up(
     stats: &mut Stats,
     player_details: &PlayerDetails,
+    #[resource] all_abilities: &AllAbilities,
     #[resource] sender: &Sender<Packet>,
 ) {
     if stats.exp >= stats.exp_next && stats.level < 100 {
@@ -95,6 +210,19 @@ pub(super) fn level_up(
             Some(Color::Cyan),
             sender,
         );
+        for ability in all_abilities
+            .iter()
+            .filter(|ability| ability.min_level == stats.level)
+        {
+            send_message_to_player(
+                &player_details.player_name,
+                &player_details.client_addr,
+                player_details.currently_online,
+                &("You can now use ".to_string() + &ability.name + "!"),
+                Some(Color::Cyan),
+                sender,
+            );
+        }
     }
 }
 
@@ -106,4 +234,5 @@ fn calculate_new_stats(stats: &mut Stats) {
     stats.stat_points += 2;
     stats.max_hp =
         (BASE_HP_TABLE[(stats.level - 1) as usize] * (1.0 + (stats.con / 100.0))).round();
+    abilities::update_max_resources(stats);
 }
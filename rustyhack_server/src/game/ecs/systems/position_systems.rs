@@ -1,8 +1,12 @@
+use crate::consts::WATER_SLOW_CHANCE_PERCENTAGE;
 use crate::game::map::state::EntityPositionMap;
 use crate::game::map::{state, tiles};
+use crate::game::rng::GameRng;
 use legion::systems::CommandBuffer;
 use legion::{Entity, maybe_changed, system};
+use rand::RngExt;
 use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::background_map::tiles::Tile;
 use rustyhack_lib::consts::DEAD_MAP;
 use rustyhack_lib::ecs::components::{
     Dead, DisplayDetails, ItemDetails, MonsterDetails, PlayerDetails, Position,
@@ -10,21 +14,45 @@ use rustyhack_lib::ecs::components::{
 use rustyhack_lib::ecs::item::{Item, get_item_name};
 use rustyhack_lib::utils::math::{i32_from, u32_from};
 
-#[system(par_for_each)]
+#[system(for_each)]
 #[filter(maybe_changed::<Position>())]
-pub(super) fn check_for_tile_collision(position: &mut Position, #[resource] all_maps: &AllMaps) {
+pub(super) fn check_for_tile_collision(
+    position: &mut Position,
+    monster_details_option: Option<&MonsterDetails>,
+    #[resource] all_maps: &AllMaps,
+    #[resource] rng: &mut GameRng,
+) {
     //no velocity, no updates
     if position.velocity_x != 0 || position.velocity_y != 0 {
         let current_map = state::get_current_map(all_maps, &position.current_map);
         let potential_pos_x = u32_from(i32_from(position.pos_x) + position.velocity_x);
         let potential_pos_y = u32_from(i32_from(position.pos_y) + position.velocity_y);
+        let potential_tile = current_map.get_tile_at(potential_pos_y, potential_pos_x);
 
-        if tiles::entity_is_colliding_with_tile(
-            current_map.get_tile_at(potential_pos_y, potential_pos_x),
-        ) {
+        if tiles::entity_is_colliding_with_tile(potential_tile) {
             debug!("Entity colliding with tile, setting velocity to 0.");
             position.velocity_x = 0;
             position.velocity_y = 0;
+        } else if tiles::is_squeezing_between_walls(
+            current_map,
+            (position.pos_x, position.pos_y),
+            (potential_pos_x, potential_pos_y),
+        ) {
+            debug!("Entity squeezing diagonally between walls, setting velocity to 0.");
+            position.velocity_x = 0;
+            position.velocity_y = 0;
+        } else if monster_details_option.is_some()
+            && !tiles::monster_can_walk_on_tile(potential_tile)
+        {
+            debug!("Monster avoiding hazardous tile, setting velocity to 0.");
+            position.velocity_x = 0;
+            position.velocity_y = 0;
+        } else if *current_map.get_tile_at(position.pos_y, position.pos_x) == Tile::Water
+            && WATER_SLOW_CHANCE_PERCENTAGE >= rng.random_range(1..=100)
+        {
+            debug!("Entity slowed by water, setting velocity to 0.");
+            position.velocity_x = 0;
+            position.velocity_y = 0;
         } else {
             debug!("Entity not colliding with tile, continuing to combat check.");
         }
@@ -76,7 +104,7 @@ pub(super) fn collate_all_monster_positions(
         (
             position.clone(),
             *display_details,
-            monster_details.monster_type.clone(),
+            monster_details.display_name(),
         ),
     );
 }
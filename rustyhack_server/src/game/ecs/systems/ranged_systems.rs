@@ -0,0 +1,174 @@
+use super::combat_systems::{get_attacker, is_defender_lit, is_hostile};
+use crate::consts::DARKNESS_ACCURACY_PENALTY;
+use crate::game::action_energy::{self, ActionEnergy};
+use crate::game::combat::{
+    self, CombatAttackerStats, CombatParties, Combatant, Defender, PendingRangedAttacks,
+};
+use crate::game::map::state::{self, AllMapStates, MapState};
+use crate::game::monsters::MonstersPositions;
+use crate::game::monsters::pathfinding;
+use crate::game::status_effects;
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use laminar::Packet;
+use legion::system;
+use rustyhack_lib::background_map::{AllMaps, BackgroundMap, field_of_view};
+use rustyhack_lib::ecs::components::{
+    Inventory, MonsterDetails, PlayerDetails, Position, Stats, StatusEffects,
+};
+use rustyhack_lib::ecs::item::{get_item_name, get_ranged_weapon};
+
+#[allow(clippy::too_many_arguments)]
+#[system(for_each)]
+pub(super) fn resolve_ranged_attacks(
+    position: &Position,
+    stats: &mut Stats,
+    inventory: &mut Inventory,
+    player_details_option: Option<&PlayerDetails>,
+    monster_details_option: Option<&MonsterDetails>,
+    status_effects_option: Option<&StatusEffects>,
+    #[resource] pending_ranged_attacks: &PendingRangedAttacks,
+    #[resource] all_maps: &AllMaps,
+    #[resource] all_map_states: &mut AllMapStates,
+    #[resource] monsters_positions: &MonstersPositions,
+    #[resource] combat_parties: &mut CombatParties,
+    #[resource] combat_attacker_stats: &mut CombatAttackerStats,
+    #[resource] action_energy: &mut ActionEnergy,
+    #[resource] sender: &Sender<Packet>,
+) {
+    let attacker = get_attacker(player_details_option, monster_details_option);
+    let Some(target) = pending_ranged_attacks.get(&attacker.id) else {
+        return;
+    };
+    if stats.current_hp <= 0.0 {
+        return;
+    }
+    let send_failure_message = |message: &str| {
+        if let Some(player_details) = player_details_option {
+            send_message_to_player(
+                &player_details.player_name,
+                &player_details.client_addr,
+                player_details.currently_online,
+                message,
+                None,
+                sender,
+            );
+        }
+    };
+
+    if status_effects_option.is_some_and(status_effects::is_movement_prevented) {
+        send_failure_message("You can't attack while stunned.");
+        return;
+    }
+    let Some(ranged_weapon) = get_ranged_weapon(inventory).cloned() else {
+        send_failure_message("You have nothing to fire.");
+        return;
+    };
+    let current_map = state::get_current_map(all_maps, &position.current_map);
+    let (defender, attack_distance) = match check_ranged_target(
+        current_map,
+        state::get_current_map_states(all_map_states, &position.current_map),
+        monsters_positions,
+        monster_details_option,
+        &Combatant {
+            is_player: attacker.is_player,
+            pvp_enabled: stats.pvp_enabled,
+            tile: (position.pos_x, position.pos_y),
+        },
+        *target,
+        ranged_weapon.range,
+    ) {
+        Ok(defender_and_distance) => defender_and_distance,
+        Err(message) => {
+            send_failure_message(message);
+            return;
+        }
+    };
+    let ammunition_index = match &ranged_weapon.ammunition {
+        Some(ammunition) => {
+            let Some(index) = inventory
+                .carried
+                .iter()
+                .position(|item| get_item_name(item) == *ammunition)
+            else {
+                send_failure_message(&("You have no ".to_string() + ammunition + " left."));
+                return;
+            };
+            Some(index)
+        }
+        None => None,
+    };
+
+    let mut attacker_inventory = inventory.clone();
+    attacker_inventory.equipped.weapon = ranged_weapon;
+    let action_cost = action_energy::calculate_attack_cost(&attacker_inventory);
+    if !action_energy::has_energy_for(action_energy, &attacker.id, action_cost) {
+        send_failure_message("You aren't ready to attack yet.");
+        return;
+    }
+    action_energy::spend_energy(action_energy, &attacker.id, action_cost);
+    if let Some(index) = ammunition_index {
+        //thrown weapons are their own ammunition, so may leave the player with nothing to throw
+        inventory.carried.remove(index);
+        attacker_inventory.carried.remove(index);
+        inventory.update_available = true;
+    }
+
+    if attacker.is_player && !is_defender_lit(current_map, *target, position, inventory) {
+        //monsters can see in the dark, but players firing blind are less accurate
+        debug!("Player firing into darkness, reducing accuracy.");
+        attacker_inventory.equipped.weapon.accuracy -= DARKNESS_ACCURACY_PENALTY;
+    }
+    debug!(
+        "Ranged attack detected, attacker is: {:?}, defender is: {:?}, distance: {attack_distance}",
+        &attacker, &defender
+    );
+    combat_parties.insert(attacker.clone(), defender);
+    combat_attacker_stats.insert(attacker.id, (*stats, attacker_inventory, attack_distance));
+    stats.in_combat = true;
+}
+
+//anything hit from a distance needs to be in range, in line of sight, and something to fight
+pub(super) fn check_ranged_target(
+    current_map: &BackgroundMap,
+    current_map_state: &MapState,
+    monsters_positions: &MonstersPositions,
+    attacker_monster_option: Option<&MonsterDetails>,
+    attacker: &Combatant,
+    target: (u32, u32),
+    range: u32,
+) -> Result<(Defender, u32), &'static str> {
+    let attacker_tile = attacker.tile;
+    let attack_distance = pathfinding::get_distance(attacker_tile, target);
+    if attack_distance == 0 || attack_distance > range {
+        return Err("That's out of range.");
+    }
+    if !field_of_view::has_line_of_sight(current_map, attacker_tile, target) {
+        return Err("You don't have a clear shot.");
+    }
+    let (is_target_found, defender) =
+        state::is_colliding_with_entity(target.0, target.1, current_map_state);
+    if !is_target_found {
+        return Err("There's nothing there to hit.");
+    }
+    if !is_hostile(attacker_monster_option, &defender, monsters_positions) {
+        return Err("You can't attack that.");
+    }
+    combat::check_combat_allowed(
+        current_map,
+        attacker,
+        &Combatant {
+            is_player: defender.is_player,
+            pvp_enabled: state::is_player_pvp_enabled_at(target.0, target.1, current_map_state),
+            tile: target,
+        },
+    )?;
+    Ok((defender, attack_distance))
+}
+
+#[system]
+pub(super) fn clear_pending_ranged_attacks(
+    #[resource] pending_ranged_attacks: &mut PendingRangedAttacks,
+) {
+    pending_ranged_attacks.clear();
+}
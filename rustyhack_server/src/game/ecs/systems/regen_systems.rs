@@ -1,5 +1,6 @@
 use crate::consts::{
     BASE_HEALTH_REGEN_PERCENT, HEALTH_REGEN_CON_PERCENT, HEALTH_REGEN_CON_STATIC_FACTOR,
+    MANA_REGEN_PERCENT, STAMINA_REGEN_PERCENT,
 };
 use legion::system;
 use rustyhack_lib::ecs::components::Stats;
@@ -26,6 +27,26 @@ pub(super) fn apply_health_regen(stats: &mut Stats) {
     }
 }
 
+//mana and stamina come back even in combat, stamina much faster than mana
+#[system(par_for_each)]
+pub(super) fn apply_mana_and_stamina_regen(stats: &mut Stats) {
+    if stats.current_hp <= 0.0 {
+        return;
+    }
+    if stats.current_mana < stats.max_mana {
+        stats.current_mana = (stats.current_mana
+            + (stats.max_mana * (MANA_REGEN_PERCENT / 100.0)).round())
+        .min(stats.max_mana);
+        stats.update_available = true;
+    }
+    if stats.current_stamina < stats.max_stamina {
+        stats.current_stamina = (stats.current_stamina
+            + (stats.max_stamina * (STAMINA_REGEN_PERCENT / 100.0)).round())
+        .min(stats.max_stamina);
+        stats.update_available = true;
+    }
+}
+
 fn calculate_regen_amount(max_hp: f32, con: f32) -> f32 {
     // Current regen calculation is as follows, this is just a first pass, it may not make sense.
     // current hp
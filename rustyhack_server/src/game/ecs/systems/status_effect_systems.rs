@@ -0,0 +1,74 @@
+use crate::game::status_effects;
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use crossterm::style::Color;
+use laminar::Packet;
+use legion::{maybe_changed, system};
+use rustyhack_lib::ecs::components::{PlayerDetails, Position, Stats, StatusEffects};
+
+#[system(for_each)]
+pub(super) fn update_status_effects(
+    player_details_option: Option<&PlayerDetails>,
+    stats: &mut Stats,
+    status_effects: &mut StatusEffects,
+    #[resource] sender: &Sender<Packet>,
+) {
+    if status_effects.active.is_empty() || stats.current_hp <= 0.0 {
+        return;
+    }
+    let mut messages: Vec<(String, Option<Color>)> = vec![];
+    for active_effect in &status_effects.active {
+        let hp_change = active_effect.effect.hp_per_tick
+            * f32::from(u16::try_from(active_effect.stacks).unwrap_or(u16::MAX));
+        if hp_change < 0.0 {
+            messages.push((
+                active_effect.effect.name.clone()
+                    + " deals "
+                    + &(-hp_change).to_string()
+                    + " damage to you!",
+                Some(Color::DarkRed),
+            ));
+        } else if hp_change > 0.0 {
+            messages.push((
+                active_effect.effect.name.clone()
+                    + " heals you for "
+                    + &hp_change.to_string()
+                    + ".",
+                Some(Color::Green),
+            ));
+        }
+    }
+    let (hp_change, expired_effects) = status_effects::update_status_effects(status_effects, stats);
+    if hp_change != 0.0 || !expired_effects.is_empty() {
+        stats.update_available = true;
+    }
+    for expired_effect in expired_effects {
+        messages.push((expired_effect.effect.name + " has worn off.", None));
+    }
+    if let Some(player_details) = player_details_option {
+        for (message, colour) in messages {
+            send_message_to_player(
+                &player_details.player_name,
+                &player_details.client_addr,
+                player_details.currently_online,
+                &message,
+                colour,
+                sender,
+            );
+        }
+    }
+}
+
+//stunned entities stay where they are, which also stops them attacking by moving into things,
+//firing and using abilities are checked separately
+#[system(par_for_each)]
+#[filter(maybe_changed::<Position>())]
+pub(super) fn prevent_stunned_movement(position: &mut Position, status_effects: &StatusEffects) {
+    if (position.velocity_x != 0 || position.velocity_y != 0)
+        && status_effects::is_movement_prevented(status_effects)
+    {
+        debug!("Entity can't move, setting velocity to 0.");
+        position.velocity_x = 0;
+        position.velocity_y = 0;
+    }
+}
@@ -0,0 +1,184 @@
+use crate::consts::BREAKABLE_WALL_HIT_POINTS;
+use crate::game::combat;
+use crate::game::map::state;
+use crate::game::map::terrain;
+use crate::game::map::terrain::{PendingTileChanges, TerrainAttack, TerrainAttacks, TerrainDamage};
+use crate::game::players;
+use crate::game::rng::GameRng;
+use crate::network_messages::send_message_to_player;
+use crossbeam_channel::Sender;
+use laminar::Packet;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::{Query, maybe_changed, system};
+use rustyhack_lib::background_map::tiles::Tile;
+use rustyhack_lib::background_map::{AllMaps, character_map};
+use rustyhack_lib::ecs::components::{Inventory, PlayerDetails, Position, Stats, TileChange};
+use rustyhack_lib::utils::math::{i32_from, u32_from};
+use std::collections::HashMap;
+
+#[system(for_each)]
+#[filter(maybe_changed::<Position>())]
+pub(super) fn check_for_terrain_attack(
+    player_details: &PlayerDetails,
+    position: &Position,
+    stats: &Stats,
+    inventory: &Inventory,
+    #[resource] all_maps: &AllMaps,
+    #[resource] terrain_attacks: &mut TerrainAttacks,
+) {
+    if (position.velocity_x == 0 && position.velocity_y == 0) || stats.current_hp <= 0.0 {
+        return;
+    }
+    let current_map = state::get_current_map(all_maps, &position.current_map);
+    let potential_pos_x = u32_from(i32_from(position.pos_x) + position.velocity_x);
+    let potential_pos_y = u32_from(i32_from(position.pos_y) + position.velocity_y);
+    if let Tile::Wall(wall) = current_map.get_tile_at(potential_pos_y, potential_pos_x)
+        && wall.is_breakable()
+    {
+        debug!(
+            "Player {} attacking breakable wall at ({}, {}).",
+            player_details.player_name, potential_pos_x, potential_pos_y
+        );
+        terrain_attacks.insert(
+            player_details.id,
+            TerrainAttack {
+                player_name: player_details.player_name.clone(),
+                client_addr: player_details.client_addr.clone(),
+                currently_online: player_details.currently_online,
+                map: position.current_map.clone(),
+                x: potential_pos_x,
+                y: potential_pos_y,
+                stats: *stats,
+                inventory: inventory.clone(),
+            },
+        );
+    } else {
+        //player has moved elsewhere, therefore no longer attacking the wall
+        terrain_attacks.remove(&player_details.id);
+    }
+}
+
+#[system]
+pub(super) fn resolve_terrain_attacks(
+    #[resource] all_maps: &AllMaps,
+    #[resource] terrain_attacks: &mut TerrainAttacks,
+    #[resource] terrain_damage: &mut TerrainDamage,
+    #[resource] pending_tile_changes: &mut PendingTileChanges,
+    #[resource] rng: &mut GameRng,
+    #[resource] sender: &Sender<Packet>,
+) {
+    for terrain_attack in terrain_attacks.values() {
+        let current_map = state::get_current_map(all_maps, &terrain_attack.map);
+        let already_broken = pending_tile_changes.iter().any(|tile_change| {
+            tile_change.map == terrain_attack.map
+                && tile_change.x == terrain_attack.x
+                && tile_change.y == terrain_attack.y
+        });
+        let Tile::Wall(wall) = current_map.get_tile_at(terrain_attack.y, terrain_attack.x) else {
+            continue;
+        };
+        if !wall.is_breakable() || already_broken {
+            continue;
+        }
+        let damage =
+            combat::resolve_terrain_damage(&terrain_attack.stats, &terrain_attack.inventory, rng);
+        let wall_key = (
+            terrain_attack.map.clone(),
+            terrain_attack.x,
+            terrain_attack.y,
+        );
+        let total_damage = terrain_damage.entry(wall_key.clone()).or_insert(0.0);
+        *total_damage += damage;
+        if *total_damage >= BREAKABLE_WALL_HIT_POINTS {
+            debug!(
+                "Player {} broke wall at ({}, {}) on {}.",
+                terrain_attack.player_name, terrain_attack.x, terrain_attack.y, terrain_attack.map
+            );
+            terrain_damage.remove(&wall_key);
+            pending_tile_changes.push(TileChange {
+                map: terrain_attack.map.clone(),
+                x: terrain_attack.x,
+                y: terrain_attack.y,
+                character: Tile::EmptySpace.character(),
+            });
+            send_message_to_player(
+                &terrain_attack.player_name,
+                &terrain_attack.client_addr,
+                terrain_attack.currently_online,
+                "You break through the wall!",
+                None,
+                sender,
+            );
+        } else {
+            send_message_to_player(
+                &terrain_attack.player_name,
+                &terrain_attack.client_addr,
+                terrain_attack.currently_online,
+                &("You hit the wall for ".to_string() + &damage.round().to_string() + " damage."),
+                None,
+                sender,
+            );
+        }
+    }
+    terrain_attacks.clear();
+}
+
+#[allow(clippy::too_many_arguments)]
+#[system]
+pub(super) fn apply_tile_changes(
+    world: &mut SubWorld,
+    tile_change_query: &mut Query<&mut TileChange>,
+    player_query: &mut Query<&PlayerDetails>,
+    commands: &mut CommandBuffer,
+    #[resource] all_maps: &mut AllMaps,
+    #[resource] terrain_damage: &mut TerrainDamage,
+    #[resource] pending_tile_changes: &mut PendingTileChanges,
+    #[resource] sender: &Sender<Packet>,
+) {
+    if pending_tile_changes.is_empty() {
+        return;
+    }
+    let mut changed_tiles: HashMap<String, Vec<(u32, u32, Tile)>> = HashMap::new();
+    for pending_tile_change in pending_tile_changes.drain(..) {
+        terrain::apply_tile_change(all_maps, &pending_tile_change);
+        terrain_damage.remove(&(
+            pending_tile_change.map.clone(),
+            pending_tile_change.x,
+            pending_tile_change.y,
+        ));
+
+        //only one saved change per tile, so the latest change always wins
+        let mut existing_tile_change = tile_change_query.iter_mut(world).find(|tile_change| {
+            tile_change.map == pending_tile_change.map
+                && tile_change.x == pending_tile_change.x
+                && tile_change.y == pending_tile_change.y
+        });
+        if let Some(tile_change) = existing_tile_change.as_mut() {
+            tile_change.character = pending_tile_change.character;
+        } else {
+            commands.push((pending_tile_change.clone(),));
+        }
+
+        changed_tiles
+            .entry(pending_tile_change.map.clone())
+            .or_default()
+            .push((
+                pending_tile_change.x,
+                pending_tile_change.y,
+                character_map::map_character_to_tile(
+                    pending_tile_change.x,
+                    pending_tile_change.y,
+                    pending_tile_change.character,
+                ),
+            ));
+    }
+
+    for player_details in player_query.iter(world) {
+        if player_details.currently_online {
+            for (map, tiles) in &changed_tiles {
+                players::send_tile_changes(map, tiles.clone(), &player_details.client_addr, sender);
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 mod array_utils;
 pub(super) mod exits;
+pub(super) mod metadata;
 pub(super) mod spawns;
 pub(super) mod state;
+pub(super) mod terrain;
 pub(super) mod tiles;
@@ -0,0 +1,108 @@
+use crate::consts;
+use crate::game::map::spawns::SpawnArea;
+use rustyhack_lib::background_map::tiles::TilePosition;
+use rustyhack_lib::consts::DEFAULT_PLAYER_VIEW_DISTANCE;
+use rustyhack_lib::utils::file;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::process;
+
+pub(crate) type AllMapMetadata = HashMap<String, MapMetadata>;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct MapMetadata {
+    #[serde(default = "default_ambient_light")]
+    pub(crate) ambient_light: u32,
+    #[serde(default)]
+    pub(crate) teleport_pads: Vec<TeleportPadDestination>,
+    //the whole map is a safe zone
+    #[serde(default)]
+    pub(crate) safe_zone: bool,
+    //safe zones use the same shapes as spawn areas
+    #[serde(default)]
+    pub(crate) safe_zones: Vec<SpawnArea>,
+    #[serde(default)]
+    pub(crate) bind_stones: Vec<TilePosition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TeleportPadDestination {
+    pub(crate) x: u32,
+    pub(crate) y: u32,
+    pub(crate) new_x: u32,
+    pub(crate) new_y: u32,
+}
+
+impl Default for MapMetadata {
+    fn default() -> Self {
+        MapMetadata {
+            ambient_light: default_ambient_light(),
+            teleport_pads: vec![],
+            safe_zone: false,
+            safe_zones: vec![],
+            bind_stones: vec![],
+        }
+    }
+}
+
+fn default_ambient_light() -> u32 {
+    DEFAULT_PLAYER_VIEW_DISTANCE
+}
+
+pub(crate) fn initialise_all_map_metadata() -> AllMapMetadata {
+    info!("About to initialise all map metadata");
+    let mut all_map_metadata: AllMapMetadata = HashMap::new();
+    let mut file_location = file::current_exe_location();
+    file_location.pop();
+    file_location.push(consts::ASSETS_DIRECTORY);
+    file_location.push(consts::MAP_METADATA_DIRECTORY);
+    let paths = file::get_all_files_in_location(&file_location);
+    for path in paths {
+        let unwrapped_path = path.unwrap();
+        let map = String::from(
+            unwrapped_path
+                .file_name()
+                .to_str()
+                .unwrap()
+                .split('.')
+                .next()
+                .unwrap(),
+        );
+        let map_metadata = get_map_metadata_from_path(&unwrapped_path.path());
+        info!("Initialised map metadata for map: {:?}", &map);
+        all_map_metadata.insert(map, map_metadata);
+    }
+    all_map_metadata
+}
+
+pub(crate) fn get_map_metadata<'a>(
+    all_map_metadata: &'a AllMapMetadata,
+    map: &str,
+    default_map_metadata: &'a MapMetadata,
+) -> &'a MapMetadata {
+    all_map_metadata.get(map).unwrap_or_else(|| {
+        warn!("No map metadata found for map: {map}, using default metadata.");
+        default_map_metadata
+    })
+}
+
+fn get_map_metadata_from_path(path: &Path) -> MapMetadata {
+    let file = File::open(path).unwrap_or_else(|err| {
+        error!(
+            "Problem getting map metadata from file: {}, error: {err}",
+            path.display()
+        );
+        process::exit(1);
+    });
+    let buf_reader = BufReader::new(file);
+    serde_json::from_reader(buf_reader).unwrap_or_else(|err| {
+        error!(
+            "Problem deserializing map metadata from file: {}, error: {err}",
+            path.display()
+        );
+        process::exit(1);
+    })
+}
@@ -1,14 +1,19 @@
 use crate::consts;
+use crate::game::time_of_day::TimeOfDay;
 use rustyhack_lib::utils::file;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 use std::process;
 
-pub(crate) type AllSpawnsMap = HashMap<String, Spawns>;
-pub(crate) type AllSpawnCounts = HashMap<String, HashMap<String, u32>>;
+//ordered by map so spawning rolls happen in the same order with a fixed random seed
+pub(crate) type AllSpawnsMap = BTreeMap<String, Spawns>;
+//alive monsters on each map, keyed by the index of their spawn entry
+pub(crate) type AllSpawnCounts = HashMap<String, HashMap<usize, u32>>;
+//ticks each spawn entry on each map has been waiting to respawn
+pub(crate) type AllSpawnTimers = HashMap<String, HashMap<usize, u32>>;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct Spawns {
@@ -18,7 +23,118 @@ pub(crate) struct Spawns {
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct MonsterSpawnPositions {
     pub(crate) monster_type: String,
+    #[serde(default)]
     pub(crate) spawn_positions: Vec<PositionWithoutMap>,
+    #[serde(default)]
+    pub(crate) spawn_areas: Vec<SpawnArea>,
+    //defaults to none, so monsters only respawn on the timer
+    #[serde(default)]
+    pub(crate) min_population: Option<u32>,
+    //defaults to one monster per spawn position
+    #[serde(default)]
+    pub(crate) max_population: Option<u32>,
+    #[serde(default = "default_respawn_ticks")]
+    pub(crate) respawn_ticks: u32,
+    #[serde(default)]
+    pub(crate) time_of_day: Option<TimeOfDay>,
+    //when missing monsters spawn at the level in their definition
+    #[serde(default)]
+    pub(crate) levels: Option<LevelRange>,
+    #[serde(default = "default_elite_chance_percentage")]
+    pub(crate) elite_chance_percentage: u32,
+}
+
+impl MonsterSpawnPositions {
+    pub(crate) fn min_population(&self) -> u32 {
+        self.min_population.unwrap_or(0)
+    }
+
+    pub(crate) fn max_population(&self) -> u32 {
+        self.max_population
+            .unwrap_or_else(|| u32::try_from(self.spawn_positions.len()).unwrap_or(u32::MAX))
+    }
+
+    pub(crate) fn get_all_spawn_tiles(&self) -> Vec<(u32, u32)> {
+        let mut spawn_tiles: Vec<(u32, u32)> = self
+            .spawn_positions
+            .iter()
+            .map(|position| (position.x, position.y))
+            .collect();
+        for spawn_area in &self.spawn_areas {
+            spawn_tiles.extend(spawn_area.get_tiles());
+        }
+        spawn_tiles
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) enum SpawnArea {
+    Rectangle {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    Polygon {
+        points: Vec<PositionWithoutMap>,
+    },
+}
+
+impl SpawnArea {
+    pub(crate) fn get_tiles(&self) -> Vec<(u32, u32)> {
+        match self {
+            SpawnArea::Rectangle {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let mut tiles = vec![];
+                for tile_y in *y..y + height {
+                    for tile_x in *x..x + width {
+                        tiles.push((tile_x, tile_y));
+                    }
+                }
+                tiles
+            }
+            SpawnArea::Polygon { points } => {
+                let min_x = points.iter().map(|point| point.x).min().unwrap_or(0);
+                let max_x = points.iter().map(|point| point.x).max().unwrap_or(0);
+                let min_y = points.iter().map(|point| point.y).min().unwrap_or(0);
+                let max_y = points.iter().map(|point| point.y).max().unwrap_or(0);
+                let mut tiles = vec![];
+                for tile_y in min_y..=max_y {
+                    for tile_x in min_x..=max_x {
+                        if is_inside_polygon(points, tile_x, tile_y) {
+                            tiles.push((tile_x, tile_y));
+                        }
+                    }
+                }
+                tiles
+            }
+        }
+    }
+}
+
+//even-odd rule, casting a ray to the right from the centre of the tile
+fn is_inside_polygon(points: &[PositionWithoutMap], x: u32, y: u32) -> bool {
+    let (x, y) = (f64::from(x) + 0.5, f64::from(y) + 0.5);
+    let mut inside = false;
+    for (index, point) in points.iter().enumerate() {
+        let next_point = &points[(index + 1) % points.len()];
+        let (x1, y1) = (f64::from(point.x) + 0.5, f64::from(point.y) + 0.5);
+        let (x2, y2) = (f64::from(next_point.x) + 0.5, f64::from(next_point.y) + 0.5);
+        if (y1 > y) != (y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LevelRange {
+    pub(crate) min: u32,
+    pub(crate) max: u32,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -27,14 +143,17 @@ pub(crate) struct PositionWithoutMap {
     pub(crate) y: u32,
 }
 
-pub(crate) fn initialise_all_spawn_definitions() -> (AllSpawnCounts, AllSpawnsMap) {
-    let all_spawns_map = get_all_spawns_positions();
-    (get_default_spawn_counts(&all_spawns_map), all_spawns_map)
+fn default_respawn_ticks() -> u32 {
+    consts::DEFAULT_RESPAWN_TICKS
+}
+
+fn default_elite_chance_percentage() -> u32 {
+    consts::DEFAULT_ELITE_CHANCE_PERCENTAGE
 }
 
-fn get_all_spawns_positions() -> AllSpawnsMap {
+pub(crate) fn initialise_all_spawn_definitions() -> AllSpawnsMap {
     info!("About to initialise all spawn positions");
-    let mut all_spawns: HashMap<String, Spawns> = HashMap::new();
+    let mut all_spawns: AllSpawnsMap = BTreeMap::new();
     let mut file_location = file::current_exe_location();
     file_location.pop();
     file_location.push(consts::ASSETS_DIRECTORY);
@@ -52,28 +171,50 @@ fn get_all_spawns_positions() -> AllSpawnsMap {
                 .unwrap(),
         );
         let spawns: Spawns = get_spawns_definition_from_path(&unwrapped_path.path());
+        check_spawns_are_valid(&map, &spawns);
         info!("Initialised spawn definitions for map: {:?}", &map);
         all_spawns.insert(map, spawns);
     }
     all_spawns
 }
 
-fn get_default_spawn_counts(all_spawns_map: &AllSpawnsMap) -> AllSpawnCounts {
-    info!("About to initialise all spawn counts");
-    let mut default_spawn_counts: HashMap<String, HashMap<String, u32>> = HashMap::new();
-    for (map, spawns) in all_spawns_map {
-        let mut map_spawn_counts: HashMap<String, u32> = HashMap::new();
-        for monster in &spawns.monsters {
-            let mut monster_spawn_count: u32 = 0;
-            for _spawn_position in &monster.spawn_positions {
-                monster_spawn_count += 1;
-            }
-            map_spawn_counts.insert(monster.monster_type.clone(), monster_spawn_count);
+fn check_spawns_are_valid(map: &str, spawns: &Spawns) {
+    for monster in &spawns.monsters {
+        if monster.get_all_spawn_tiles().is_empty() {
+            exit_with_invalid_spawn(map, monster, "no spawn positions or spawn areas");
+        }
+        if monster.spawn_positions.is_empty() && monster.max_population.is_none() {
+            exit_with_invalid_spawn(map, monster, "spawn areas need a max population");
+        }
+        if monster.min_population() > monster.max_population() {
+            exit_with_invalid_spawn(map, monster, "min population is above max population");
+        }
+        if monster.spawn_areas.iter().any(
+            |spawn_area| matches!(spawn_area, SpawnArea::Polygon { points } if points.len() < 3),
+        ) {
+            exit_with_invalid_spawn(map, monster, "polygons need at least 3 points");
+        }
+        if let Some(levels) = monster.levels
+            && (levels.min == 0 || levels.min > levels.max)
+        {
+            exit_with_invalid_spawn(
+                map,
+                monster,
+                &("invalid level range ".to_string()
+                    + &levels.min.to_string()
+                    + "-"
+                    + &levels.max.to_string()),
+            );
         }
-        default_spawn_counts.insert(map.clone(), map_spawn_counts);
     }
-    info!("All spawn counts are: {default_spawn_counts:?}");
-    default_spawn_counts
+}
+
+fn exit_with_invalid_spawn(map: &str, monster: &MonsterSpawnPositions, reason: &str) -> ! {
+    error!(
+        "Invalid spawn for monster {} in spawns for map: {map}, {reason}.",
+        monster.monster_type
+    );
+    process::exit(1);
 }
 
 fn get_spawns_definition_from_path(path: &Path) -> Spawns {
@@ -118,7 +118,7 @@ pub(crate) fn is_colliding_with_entity(x: u32, y: u32, map_state: &MapState) ->
                     colliding = true;
                     defender = Defender {
                         id: monster.monster_details.id,
-                        name: monster.monster_details.monster_type.clone(),
+                        name: monster.monster_details.display_name(),
                         client_addr: String::new(),
                         currently_online: true,
                         is_player: false,
@@ -130,6 +130,17 @@ pub(crate) fn is_colliding_with_entity(x: u32, y: u32, map_state: &MapState) ->
     }
 }
 
+//a player that isn't there counts as having pvp turned off
+pub(crate) fn is_player_pvp_enabled_at(x: u32, y: u32, map_state: &MapState) -> bool {
+    map_state
+        .get((y as usize, x as usize))
+        .is_some_and(|entity_vec| {
+            entity_vec.iter().any(|entity_type| {
+                matches!(entity_type, EntityType::Player(player) if player.stats.pvp_enabled)
+            })
+        })
+}
+
 pub(crate) fn get_current_map<'a>(all_maps: &'a AllMaps, map: &str) -> &'a BackgroundMap {
     all_maps.get(map).unwrap_or_else(|| {
         error!("Entity is located on a map that does not exist: {}", &map);
@@ -0,0 +1,51 @@
+use legion::{IntoQuery, World};
+use rustyhack_lib::background_map::{AllMaps, character_map};
+use rustyhack_lib::ecs::components::{Inventory, Stats, TileChange};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub(crate) type TerrainAttacks = HashMap<Uuid, TerrainAttack>;
+pub(crate) type TerrainDamage = HashMap<(String, u32, u32), f32>;
+pub(crate) type PendingTileChanges = Vec<TileChange>;
+
+#[derive(Clone, Debug)]
+pub(crate) struct TerrainAttack {
+    pub(crate) player_name: String,
+    pub(crate) client_addr: String,
+    pub(crate) currently_online: bool,
+    pub(crate) map: String,
+    pub(crate) x: u32,
+    pub(crate) y: u32,
+    pub(crate) stats: Stats,
+    pub(crate) inventory: Inventory,
+}
+
+pub(crate) fn apply_tile_change(all_maps: &mut AllMaps, tile_change: &TileChange) {
+    match all_maps.get_mut(&tile_change.map).and_then(|map| {
+        map.data
+            .get_mut((tile_change.y as usize, tile_change.x as usize))
+    }) {
+        Some(tile) => {
+            *tile = character_map::map_character_to_tile(
+                tile_change.x,
+                tile_change.y,
+                tile_change.character,
+            );
+        }
+        None => {
+            warn!(
+                "Tried to change tile at invalid position, map: {}, x: {}, y: {}, will try to continue.",
+                tile_change.map, tile_change.x, tile_change.y
+            );
+        }
+    }
+}
+
+pub(crate) fn apply_saved_tile_changes(world: &mut World, all_maps: &mut AllMaps) {
+    info!("About to apply saved tile changes to all maps.");
+    let mut query = <&TileChange>::query();
+    for tile_change in query.iter(world) {
+        apply_tile_change(all_maps, tile_change);
+    }
+    info!("Finished applying saved tile changes.");
+}
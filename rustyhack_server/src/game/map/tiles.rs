@@ -1,15 +1,18 @@
 use crate::consts;
 use crate::game::map::array_utils;
+use crate::game::map::metadata;
+use crate::game::map::metadata::{AllMapMetadata, MapMetadata};
+use crate::game::map::spawns::SpawnArea;
 use ndarray::Array2;
 use rustyhack_lib::background_map::AllMaps;
-use rustyhack_lib::background_map::tiles::{Collidable, Tile};
+use rustyhack_lib::background_map::tiles::{Collidable, Tile, TilePosition};
 use rustyhack_lib::background_map::{BackgroundMap, character_map};
 use rustyhack_lib::utils::file;
 use std::collections::HashMap;
 use std::path::Path;
 use std::{fs, process};
 
-pub(crate) fn initialise_all_maps() -> AllMaps {
+pub(crate) fn initialise_all_maps(all_map_metadata: &AllMapMetadata) -> AllMaps {
     info!("About to initialise all maps");
     let mut all_maps: AllMaps = HashMap::new();
     let mut file_location = file::current_exe_location();
@@ -17,6 +20,7 @@ pub(crate) fn initialise_all_maps() -> AllMaps {
     file_location.push(consts::ASSETS_DIRECTORY);
     file_location.push(consts::MAPS_DIRECTORY);
     let paths = file::get_all_files_in_location(&file_location);
+    let default_map_metadata = MapMetadata::default();
     for path in paths {
         let unwrapped_path = path.unwrap();
         let filename = String::from(
@@ -28,7 +32,10 @@ pub(crate) fn initialise_all_maps() -> AllMaps {
                 .next()
                 .unwrap(),
         );
-        let map = initialise_map(&unwrapped_path.path());
+        let map = initialise_map(
+            &unwrapped_path.path(),
+            metadata::get_map_metadata(all_map_metadata, &filename, &default_map_metadata),
+        );
         info!("Initialised map: {:?}", &filename);
         all_maps.insert(filename, map);
     }
@@ -36,10 +43,21 @@ pub(crate) fn initialise_all_maps() -> AllMaps {
     all_maps
 }
 
-fn initialise_map(path: &Path) -> BackgroundMap {
+fn initialise_map(path: &Path, map_metadata: &MapMetadata) -> BackgroundMap {
     let unprocessed_map_data = load_map_data_from_file(path);
-    let data = process_map_data(&unprocessed_map_data);
-    BackgroundMap { data }
+    let mut data = process_map_data(&unprocessed_map_data);
+    add_teleport_pad_destinations(&mut data, map_metadata);
+    add_bind_stones(&mut data, map_metadata);
+    BackgroundMap {
+        data,
+        ambient_light: map_metadata.ambient_light,
+        safe_zone: map_metadata.safe_zone,
+        safe_zone_tiles: map_metadata
+            .safe_zones
+            .iter()
+            .flat_map(SpawnArea::get_tiles)
+            .collect(),
+    }
 }
 
 fn load_map_data_from_file(path: &Path) -> String {
@@ -93,11 +111,65 @@ fn process_map_data(data: &str) -> Array2<Tile> {
     array_utils::vec_to_array(&processed_data)
 }
 
+fn add_teleport_pad_destinations(data: &mut Array2<Tile>, map_metadata: &MapMetadata) {
+    for teleport_pad_destination in &map_metadata.teleport_pads {
+        match data.get_mut((
+            teleport_pad_destination.y as usize,
+            teleport_pad_destination.x as usize,
+        )) {
+            Some(Tile::TeleportPad(teleport_pad)) => {
+                teleport_pad.destination = Some(TilePosition {
+                    x: teleport_pad_destination.new_x,
+                    y: teleport_pad_destination.new_y,
+                });
+            }
+            _ => {
+                warn!(
+                    "Teleport pad destination defined at x: {}, y: {} but there is no teleport pad there.",
+                    teleport_pad_destination.x, teleport_pad_destination.y
+                );
+            }
+        }
+    }
+}
+
+fn add_bind_stones(data: &mut Array2<Tile>, map_metadata: &MapMetadata) {
+    for bind_stone in &map_metadata.bind_stones {
+        match data.get_mut((bind_stone.y as usize, bind_stone.x as usize)) {
+            Some(tile) if *tile == Tile::EmptySpace => *tile = Tile::BindStone,
+            _ => {
+                warn!(
+                    "Bind stone defined at x: {}, y: {} but that is not an empty space.",
+                    bind_stone.x, bind_stone.y
+                );
+            }
+        }
+    }
+}
+
+//monsters don't set off traps, so they walk over them rather than giving away where they are
+pub(crate) fn monster_can_walk_on_tile(tile: &Tile) -> bool {
+    !entity_is_colliding_with_tile(tile)
+        && !matches!(tile, Tile::Lava | Tile::Acid | Tile::TeleportPad(_))
+}
+
 pub(crate) fn entity_is_colliding_with_tile(tile: &Tile) -> bool {
     match tile {
         Tile::Door(door) => door.collidable == Collidable::True,
         Tile::Wall(wall) => wall.collidable == Collidable::True,
-        Tile::Boundary => true,
+        Tile::Boundary | Tile::Torch => true,
         _ => false,
     }
 }
+
+//diagonal moves aren't allowed to squeeze through the gap between two diagonal walls
+pub(crate) fn is_squeezing_between_walls(
+    current_map: &BackgroundMap,
+    from: (u32, u32),
+    to: (u32, u32),
+) -> bool {
+    from.0 != to.0
+        && from.1 != to.1
+        && entity_is_colliding_with_tile(current_map.get_tile_at(from.1, to.0))
+        && entity_is_colliding_with_tile(current_map.get_tile_at(to.1, from.0))
+}
@@ -1,7 +1,13 @@
+pub(super) mod behaviour;
+pub(super) mod bosses;
+pub(super) mod loot;
 pub(super) mod movement;
+pub(super) mod pathfinding;
 pub(super) mod spawning;
+pub(super) mod threat;
 
 use crate::consts;
+use rustyhack_lib::ecs::components::Position;
 use rustyhack_lib::ecs::monster::{AllMonsterDefinitions, Monster};
 use rustyhack_lib::utils::file;
 use std::collections::HashMap;
@@ -9,6 +15,10 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 use std::process;
+use uuid::Uuid;
+
+//positions and faction names of all living monsters, used for monsters targeting each other
+pub(crate) type MonstersPositions = HashMap<Uuid, (Position, String)>;
 
 pub(super) fn initialise_all_monster_definitions() -> AllMonsterDefinitions {
     info!("About to initialise all monster definitions");
@@ -0,0 +1,301 @@
+use crate::consts::MONSTER_WANDER_CHANCE_PERCENTAGE;
+use crate::game::combat::PendingRangedAttacks;
+use crate::game::map::state::{self, AllMapStates};
+use crate::game::monsters::MonstersPositions;
+use crate::game::monsters::movement;
+use crate::game::monsters::pathfinding::{self, MonsterPaths};
+use crate::game::monsters::threat::{self, ThreatTables};
+use crate::game::players::PlayersPositions;
+use crate::game::rng::GameRng;
+use rand::RngExt;
+use rustyhack_lib::background_map::{AllMaps, field_of_view};
+use rustyhack_lib::consts::PLAYER_FACTION;
+use rustyhack_lib::ecs::components::{Inventory, MonsterDetails, Position, Stats};
+use rustyhack_lib::ecs::item::get_ranged_weapon;
+use rustyhack_lib::ecs::monster::BehaviourProfile;
+use rustyhack_lib::utils::math::{i32_from, u32_from};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_monster_velocity(
+    monster: &mut MonsterDetails,
+    position: &mut Position,
+    stats: &Stats,
+    inventory: &Inventory,
+    players_positions: &PlayersPositions,
+    monsters_positions: &MonstersPositions,
+    all_maps: &AllMaps,
+    all_map_states: &AllMapStates,
+    monster_paths: &mut MonsterPaths,
+    threat_tables: &mut ThreatTables,
+    pending_ranged_attacks: &mut PendingRangedAttacks,
+    rng: &mut GameRng,
+) {
+    let behaviour = &monster.behaviour;
+    //return to spawn if the monster has been drawn past its leash range
+    if movement::check_if_outside_spawn_range(
+        &monster.spawn_position,
+        position,
+        behaviour.leash_range,
+    ) {
+        debug!("Monster returning to spawn location.");
+        monster.current_target = None;
+        threat_tables.remove(&monster.id);
+        pathfinding::move_along_path(
+            monster.id,
+            position,
+            &monster.spawn_position,
+            all_maps,
+            all_map_states,
+            monster_paths,
+            rng,
+        );
+        return;
+    }
+
+    //passive monsters only build up threat from being attacked
+    let nearby_targets =
+        get_hostile_targets_nearby(monster, position, players_positions, monsters_positions);
+    let threat_table = threat_tables.entry(monster.id).or_default();
+    threat::refresh_threat_table(
+        threat_table,
+        players_positions,
+        monsters_positions,
+        &nearby_targets,
+        position,
+        behaviour.profile != BehaviourProfile::PassiveUntilAttacked,
+    );
+    monster.current_target =
+        threat::get_highest_threat_target(threat_table, monster.current_target);
+
+    match monster.current_target.and_then(|target| {
+        threat::get_target_position(target, players_positions, monsters_positions)
+    }) {
+        Some(target_position)
+            if is_ranged_attack_possible(
+                monster,
+                position,
+                inventory,
+                target_position,
+                all_maps,
+            ) =>
+        {
+            debug!("Monster firing at target.");
+            pending_ranged_attacks
+                .insert(monster.id, (target_position.pos_x, target_position.pos_y));
+            stand_still(position);
+        }
+        Some(target_position) => {
+            move_towards_or_away_from_target(
+                monster,
+                position,
+                stats,
+                target_position,
+                all_maps,
+                all_map_states,
+                monster_paths,
+                rng,
+            );
+        }
+        None => move_without_target(
+            monster,
+            position,
+            all_maps,
+            all_map_states,
+            monster_paths,
+            rng,
+        ),
+    }
+}
+
+//decays threat and adds proximity threat, once per server tick
+pub(crate) fn update_monster_threat(
+    monster: &MonsterDetails,
+    position: &Position,
+    players_positions: &PlayersPositions,
+    monsters_positions: &MonstersPositions,
+    threat_tables: &mut ThreatTables,
+) {
+    let Some(threat_table) = threat_tables.get_mut(&monster.id) else {
+        return;
+    };
+    let nearby_targets =
+        get_hostile_targets_nearby(monster, position, players_positions, monsters_positions);
+    threat::update_threat_table(
+        threat_table,
+        players_positions,
+        monsters_positions,
+        &nearby_targets,
+        position,
+        monster.behaviour.profile != BehaviourProfile::PassiveUntilAttacked,
+    );
+}
+
+//players and monsters from factions this monster attacks on sight
+fn get_hostile_targets_nearby(
+    monster: &MonsterDetails,
+    position: &Position,
+    players_positions: &PlayersPositions,
+    monsters_positions: &MonstersPositions,
+) -> HashMap<Uuid, Position> {
+    let aggro_radius = monster.behaviour.aggro_radius;
+    let mut nearby_targets = if monster.faction.is_hostile_to(PLAYER_FACTION) {
+        movement::get_all_players_nearby(players_positions, position, aggro_radius)
+    } else {
+        HashMap::new()
+    };
+    nearby_targets.extend(movement::get_all_hostile_monsters_nearby(
+        monsters_positions,
+        monster,
+        position,
+        aggro_radius,
+    ));
+    nearby_targets
+}
+
+//kiters with something to fire shoot from their preferred distance rather than closing in
+fn is_ranged_attack_possible(
+    monster: &MonsterDetails,
+    position: &Position,
+    inventory: &Inventory,
+    target_position: &Position,
+    all_maps: &AllMaps,
+) -> bool {
+    let Some(ranged_weapon) = get_ranged_weapon(inventory) else {
+        return false;
+    };
+    let monster_tile = (position.pos_x, position.pos_y);
+    let target_tile = (target_position.pos_x, target_position.pos_y);
+    let target_distance = pathfinding::get_distance(monster_tile, target_tile);
+    monster.behaviour.profile == BehaviourProfile::RangedKiter
+        && position.current_map == target_position.current_map
+        && target_distance >= monster.behaviour.preferred_distance.max(1)
+        && target_distance <= ranged_weapon.range
+        && field_of_view::has_line_of_sight(
+            state::get_current_map(all_maps, &position.current_map),
+            monster_tile,
+            target_tile,
+        )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn move_towards_or_away_from_target(
+    monster: &MonsterDetails,
+    position: &mut Position,
+    stats: &Stats,
+    target_position: &Position,
+    all_maps: &AllMaps,
+    all_map_states: &AllMapStates,
+    monster_paths: &mut MonsterPaths,
+    rng: &mut GameRng,
+) {
+    let behaviour = &monster.behaviour;
+    let target_distance = pathfinding::get_distance(
+        (position.pos_x, position.pos_y),
+        (target_position.pos_x, target_position.pos_y),
+    );
+    let is_fleeing = behaviour.profile == BehaviourProfile::Cowardly
+        && stats.current_hp / stats.max_hp * 100.0 <= f32::from(behaviour.flee_hp_percentage);
+    let is_kiting = behaviour.profile == BehaviourProfile::RangedKiter
+        && target_distance < behaviour.preferred_distance;
+
+    if is_fleeing || is_kiting {
+        debug!("Monster moving away from target.");
+        monster_paths.remove(&monster.id);
+        pathfinding::move_away_from_target(
+            position,
+            target_position,
+            all_maps,
+            all_map_states,
+            rng,
+        );
+    } else if behaviour.profile == BehaviourProfile::RangedKiter
+        && target_distance == behaviour.preferred_distance
+    {
+        debug!("Monster holding position at preferred distance from target.");
+        stand_still(position);
+    } else {
+        debug!("Monster moving towards target.");
+        pathfinding::move_along_path(
+            monster.id,
+            position,
+            target_position,
+            all_maps,
+            all_map_states,
+            monster_paths,
+            rng,
+        );
+    }
+}
+
+fn move_without_target(
+    monster: &mut MonsterDetails,
+    position: &mut Position,
+    all_maps: &AllMaps,
+    all_map_states: &AllMapStates,
+    monster_paths: &mut MonsterPaths,
+    rng: &mut GameRng,
+) {
+    let destination = match monster.behaviour.profile {
+        BehaviourProfile::StationaryGuard => Some(monster.spawn_position.clone()),
+        BehaviourProfile::Patrol if !monster.behaviour.patrol_waypoints.is_empty() => {
+            Some(get_next_waypoint(monster, position))
+        }
+        _ => None,
+    };
+
+    match destination {
+        Some(destination)
+            if destination.pos_x == position.pos_x && destination.pos_y == position.pos_y =>
+        {
+            debug!("Monster standing guard.");
+            stand_still(position);
+        }
+        Some(destination) => {
+            debug!("Monster moving towards guard or patrol location.");
+            pathfinding::move_along_path(
+                monster.id,
+                position,
+                &destination,
+                all_maps,
+                all_map_states,
+                monster_paths,
+                rng,
+            );
+        }
+        None if MONSTER_WANDER_CHANCE_PERCENTAGE >= rng.random_range(1..=100) => {
+            debug!("Monster moving randomly.");
+            monster_paths.remove(&monster.id);
+            movement::move_randomly(position, rng);
+        }
+        None => stand_still(position),
+    }
+}
+
+//advances to the following waypoint once the current one has been reached
+fn get_next_waypoint(monster: &mut MonsterDetails, position: &Position) -> Position {
+    let waypoints = &monster.behaviour.patrol_waypoints;
+    let mut waypoint = get_waypoint_position(
+        &monster.spawn_position,
+        waypoints[monster.next_waypoint % waypoints.len()],
+    );
+    if waypoint.pos_x == position.pos_x && waypoint.pos_y == position.pos_y {
+        monster.next_waypoint = (monster.next_waypoint + 1) % waypoints.len();
+        waypoint = get_waypoint_position(&monster.spawn_position, waypoints[monster.next_waypoint]);
+    }
+    waypoint
+}
+
+fn get_waypoint_position(spawn_position: &Position, offset: (i32, i32)) -> Position {
+    Position {
+        pos_x: u32_from(i32_from(spawn_position.pos_x) + offset.0),
+        pos_y: u32_from(i32_from(spawn_position.pos_y) + offset.1),
+        ..spawn_position.clone()
+    }
+}
+
+fn stand_still(position: &mut Position) {
+    position.velocity_x = 0;
+    position.velocity_y = 0;
+}
@@ -0,0 +1,113 @@
+use crate::consts::BOSS_MAX_LIVE_SUMMONS;
+use crate::game::monsters::MonstersPositions;
+use rustyhack_lib::ecs::components::{BossState, Inventory, MonsterDetails, Position, Stats};
+use rustyhack_lib::ecs::inventory::Weapon;
+use rustyhack_lib::ecs::monster::{BossAbility, BossDetails};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+pub(crate) type PendingAreaAttacks = Vec<AreaAttack>;
+pub(crate) type PendingAnnouncements = Vec<Announcement>;
+//summoned monsters to remove, because their boss died or gave up the fight
+pub(crate) type DismissedSummons = HashSet<Uuid>;
+
+//resolved alongside area abilities on the next action tick, so it goes through the normal combat rules
+#[derive(Clone, Debug)]
+pub(crate) struct AreaAttack {
+    pub(crate) monster: MonsterDetails,
+    pub(crate) position: Position,
+    pub(crate) radius: u32,
+    pub(crate) stats: Stats,
+    pub(crate) inventory: Inventory,
+}
+
+//sent to every online player, or only those on the given map
+#[derive(Clone, Debug)]
+pub(crate) struct Announcement {
+    pub(crate) map: Option<String>,
+    pub(crate) message: String,
+}
+
+//phases are entered in order as hp drops, so a big hit can move through several at once
+pub(crate) fn get_new_phases(
+    boss: &BossDetails,
+    boss_state: &BossState,
+    stats: &Stats,
+) -> Vec<usize> {
+    let hp_percentage = stats.current_hp / stats.max_hp * 100.0;
+    boss.phases
+        .iter()
+        .enumerate()
+        .skip(boss_state.current_phase + 1)
+        .take_while(|(_index, phase)| hp_percentage <= f32::from(phase.hp_threshold_percentage))
+        .map(|(index, _phase)| index)
+        .collect()
+}
+
+pub(crate) fn apply_enrage(boss: &BossDetails, phase: usize, stats: &mut Stats) {
+    for ability in &boss.phases[phase].abilities {
+        if let BossAbility::Enrage {
+            str_bonus,
+            dex_bonus,
+        } = ability
+        {
+            debug!("Boss enraged, adding {str_bonus} str and {dex_bonus} dex.");
+            stats.str += f32::from(*str_bonus);
+            stats.dex += f32::from(*dex_bonus);
+        }
+    }
+}
+
+//abilities other than enrage are used every few ticks while the boss has a target
+pub(crate) fn is_ability_ready(monster: &MonsterDetails, boss_state: &mut BossState) -> bool {
+    let Some(boss) = monster.boss.as_ref() else {
+        return false;
+    };
+    if monster.current_target.is_none() {
+        boss_state.ticks_since_ability = 0;
+        return false;
+    }
+    let ability_interval_ticks = boss
+        .phases
+        .get(boss_state.current_phase)
+        .map_or(u32::MAX, |phase| phase.ability_interval_ticks);
+    boss_state.ticks_since_ability += 1;
+    if boss_state.ticks_since_ability >= ability_interval_ticks {
+        boss_state.ticks_since_ability = 0;
+        true
+    } else {
+        false
+    }
+}
+
+//forgets summons that have died, returning how many more can be summoned
+pub(crate) fn get_summons_available(
+    boss_state: &mut BossState,
+    monsters_positions: &MonstersPositions,
+) -> usize {
+    boss_state
+        .summons
+        .retain(|summon_id| monsters_positions.contains_key(summon_id));
+    BOSS_MAX_LIVE_SUMMONS.saturating_sub(boss_state.summons.len())
+}
+
+pub(crate) fn dismiss_summons(
+    boss_state: &mut BossState,
+    dismissed_summons: &mut DismissedSummons,
+) {
+    dismissed_summons.extend(boss_state.summons.drain(..));
+}
+
+//area attacks hit like a weapon that never misses, so armour, resistances and blocks still apply
+pub(crate) fn get_area_attack_inventory(inventory: &Inventory, damage: u16) -> Inventory {
+    let mut area_attack_inventory = inventory.clone();
+    let damage = f32::from(damage);
+    area_attack_inventory.equipped.weapon = Weapon {
+        name: "Area Attack".to_string(),
+        damage_range: damage..damage + 1.0,
+        accuracy: 100.0,
+        damage_type: inventory.equipped.weapon.damage_type,
+        ..Weapon::default()
+    };
+    area_attack_inventory
+}
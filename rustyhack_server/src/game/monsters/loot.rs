@@ -0,0 +1,196 @@
+use crate::consts;
+use crate::game::rng::GameRng;
+use crossterm::style::Color;
+use rand::RngExt;
+use rustyhack_lib::consts::DEFAULT_ITEM_COLOUR;
+use rustyhack_lib::ecs::item::Item;
+use rustyhack_lib::utils::file;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::process;
+
+//loot tables are keyed by file name, a monster drops from the table matching its monster type
+pub(crate) type AllLootTables = HashMap<String, LootTable>;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LootTable {
+    #[serde(default = "default_rolls")]
+    pub(crate) rolls: u32,
+    pub(crate) entries: Vec<LootEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LootEntry {
+    pub(crate) weight: u32,
+    #[serde(default)]
+    pub(crate) rarity: Rarity,
+    pub(crate) drop: LootDrop,
+    #[serde(default = "default_quantity")]
+    pub(crate) min_quantity: u32,
+    #[serde(default = "default_quantity")]
+    pub(crate) max_quantity: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) enum LootDrop {
+    Nothing,
+    Item(Item),
+    Table(String),
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+impl Rarity {
+    pub(crate) fn colour(self) -> Color {
+        match self {
+            Rarity::Common => DEFAULT_ITEM_COLOUR,
+            Rarity::Uncommon => Color::Green,
+            Rarity::Rare => Color::Blue,
+            Rarity::Epic => Color::Magenta,
+            Rarity::Legendary => Color::Yellow,
+        }
+    }
+}
+
+fn default_rolls() -> u32 {
+    1
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+pub(crate) fn initialise_all_loot_tables() -> AllLootTables {
+    info!("About to initialise all loot tables");
+    let mut all_loot_tables: AllLootTables = HashMap::new();
+    let mut file_location = file::current_exe_location();
+    file_location.pop();
+    file_location.push(consts::ASSETS_DIRECTORY);
+    file_location.push(consts::LOOT_TABLES_DIRECTORY);
+    let paths = file::get_all_files_in_location(&file_location);
+    for path in paths {
+        let unwrapped_path = path.unwrap();
+        let name = String::from(
+            unwrapped_path
+                .file_name()
+                .to_str()
+                .unwrap()
+                .split('.')
+                .next()
+                .unwrap(),
+        );
+        let loot_table: LootTable = get_loot_table_from_path(&unwrapped_path.path());
+        info!("Initialised loot table: {:?}", &name);
+        all_loot_tables.insert(name, loot_table);
+    }
+    check_nested_tables_exist(&all_loot_tables);
+    all_loot_tables
+}
+
+fn get_loot_table_from_path(path: &Path) -> LootTable {
+    let file = File::open(path).unwrap_or_else(|err| {
+        error!(
+            "Problem getting loot table from file: {}, error: {err}",
+            path.display()
+        );
+        process::exit(1);
+    });
+    let buf_reader = BufReader::new(file);
+    serde_json::from_reader(buf_reader).unwrap_or_else(|err| {
+        error!(
+            "Problem deserializing loot table from file: {}, error: {err}",
+            path.display()
+        );
+        process::exit(1);
+    })
+}
+
+fn check_nested_tables_exist(all_loot_tables: &AllLootTables) {
+    for (name, loot_table) in all_loot_tables {
+        for entry in &loot_table.entries {
+            if let LootDrop::Table(nested_name) = &entry.drop
+                && !all_loot_tables.contains_key(nested_name)
+            {
+                error!("Loot table {name} refers to missing loot table {nested_name}.");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+pub(crate) fn roll_loot(
+    all_loot_tables: &AllLootTables,
+    table_name: &str,
+    rng: &mut GameRng,
+) -> Vec<(Item, Rarity)> {
+    let mut loot = vec![];
+    roll_loot_table(all_loot_tables, table_name, 0, &mut loot, rng);
+    debug!("Rolled loot from table {table_name}: {loot:?}");
+    loot
+}
+
+fn roll_loot_table(
+    all_loot_tables: &AllLootTables,
+    table_name: &str,
+    depth: u32,
+    loot: &mut Vec<(Item, Rarity)>,
+    rng: &mut GameRng,
+) {
+    //stops tables that refer back to each other from looping forever
+    if depth > consts::MAX_LOOT_TABLE_DEPTH {
+        warn!("Loot table {table_name} nested too deeply, skipping.");
+        return;
+    }
+    let Some(loot_table) = all_loot_tables.get(table_name) else {
+        return;
+    };
+    for _ in 0..loot_table.rolls {
+        let Some(entry) = pick_weighted_entry(&loot_table.entries, rng) else {
+            continue;
+        };
+        let quantity =
+            rng.random_range(entry.min_quantity..=entry.max_quantity.max(entry.min_quantity));
+        match &entry.drop {
+            LootDrop::Nothing => {}
+            LootDrop::Item(Item::Gold(amount)) => {
+                loot.push((Item::Gold(amount * quantity), entry.rarity));
+            }
+            LootDrop::Item(item) => {
+                for _ in 0..quantity {
+                    loot.push((item.clone(), entry.rarity));
+                }
+            }
+            LootDrop::Table(nested_table_name) => {
+                for _ in 0..quantity {
+                    roll_loot_table(all_loot_tables, nested_table_name, depth + 1, loot, rng);
+                }
+            }
+        }
+    }
+}
+
+fn pick_weighted_entry<'a>(entries: &'a [LootEntry], rng: &mut GameRng) -> Option<&'a LootEntry> {
+    let total_weight: u32 = entries.iter().map(|entry| entry.weight).sum();
+    if total_weight == 0 {
+        return None;
+    }
+    let mut roll = rng.random_range(0..total_weight);
+    for entry in entries {
+        if roll < entry.weight {
+            return Some(entry);
+        }
+        roll -= entry.weight;
+    }
+    None
+}
@@ -1,13 +1,18 @@
-use crate::consts::MONSTER_DISTANCE_ACTIVATION;
+use crate::game::monsters::MonstersPositions;
 use crate::game::players::PlayersPositions;
+use crate::game::rng::GameRng;
 use rand::RngExt;
 use rayon::prelude::*;
-use rustyhack_lib::ecs::components::Position;
+use rustyhack_lib::ecs::components::{MonsterDetails, Position};
 use rustyhack_lib::utils::math::i32_from;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-pub(crate) fn move_towards_target(monster_position: &mut Position, target_position: &Position) {
+pub(crate) fn move_towards_target(
+    monster_position: &mut Position,
+    target_position: &Position,
+    rng: &mut GameRng,
+) {
     let monster_position_x = i32_from(monster_position.pos_x);
     let monster_position_y = i32_from(monster_position.pos_y);
     let diff_x: i32 = monster_position_x - i32_from(target_position.pos_x);
@@ -15,9 +20,12 @@ pub(crate) fn move_towards_target(monster_position: &mut Position, target_positi
     let mut new_pos_x = monster_position_x;
     let mut new_pos_y = monster_position_y;
 
-    if (diff_x.abs() >= 1 && diff_y.abs() >= 1) || (diff_x == 0 && diff_y == 0) {
-        //far away, move randomly towards
-        let mut rng = rand::rng();
+    if diff_x.abs() >= 1 && diff_y.abs() >= 1 {
+        //not in line, move diagonally towards
+        new_pos_x = move_towards(diff_x, monster_position_x);
+        new_pos_y = move_towards(diff_y, monster_position_y);
+    } else if diff_x == 0 && diff_y == 0 {
+        //already there, move randomly
         if rng.random::<bool>() {
             new_pos_x = move_towards(diff_x, monster_position_x);
         } else {
@@ -25,7 +33,6 @@ pub(crate) fn move_towards_target(monster_position: &mut Position, target_positi
         }
     } else if diff_x.abs() > 1 && diff_y.abs() == 0 {
         //in line, should mostly move towards, but sometimes randomly
-        let mut rng = rand::rng();
         if rng.random_range(1..=6) > 1 {
             new_pos_x = move_towards(diff_x, monster_position_x);
         } else if rng.random::<bool>() {
@@ -35,7 +42,6 @@ pub(crate) fn move_towards_target(monster_position: &mut Position, target_positi
         }
     } else if diff_x.abs() == 0 && diff_y.abs() > 1 {
         //in line, should mostly move towards, but sometimes randomly
-        let mut rng = rand::rng();
         if rng.random_range(1..=6) > 1 {
             new_pos_y = move_towards(diff_y, monster_position_y);
         } else if rng.random::<bool>() {
@@ -55,37 +61,15 @@ pub(crate) fn move_towards_target(monster_position: &mut Position, target_positi
     monster_position.velocity_y = new_pos_y - monster_position_y;
 }
 
-pub(crate) fn get_nearest_target(
-    nearby_players: &HashMap<Uuid, Position>,
-    monster_position: &Position,
-) -> Uuid {
-    let mut closest_distance: u32 = u32::MAX;
-    let mut closest_uuid = Uuid::new_v4();
-    for (uuid, target_position) in nearby_players {
-        let diff_x: u32 =
-            (i32_from(monster_position.pos_x) - i32_from(target_position.pos_x)).unsigned_abs();
-        let diff_y: u32 =
-            (i32_from(monster_position.pos_y) - i32_from(target_position.pos_y)).unsigned_abs();
-        if diff_x < closest_distance {
-            closest_distance = diff_x;
-            closest_uuid = *uuid;
-        }
-        if diff_y < closest_distance {
-            closest_distance = diff_y;
-            closest_uuid = *uuid;
-        }
-    }
-    closest_uuid
-}
-
 pub(crate) fn check_if_outside_spawn_range(
     spawn_position: &Position,
     current_position: &Position,
+    leash_range: u32,
 ) -> bool {
     let diff_x: i32 = i32_from(current_position.pos_x) - i32_from(spawn_position.pos_x);
     let diff_y: i32 = i32_from(current_position.pos_y) - i32_from(spawn_position.pos_y);
 
-    diff_x.abs() > MONSTER_DISTANCE_ACTIVATION || diff_y.abs() > MONSTER_DISTANCE_ACTIVATION
+    diff_x.unsigned_abs() > leash_range || diff_y.unsigned_abs() > leash_range
 }
 
 fn move_towards(diff: i32, position: i32) -> i32 {
@@ -96,20 +80,14 @@ fn move_towards(diff: i32, position: i32) -> i32 {
     }
 }
 
-pub(crate) fn move_randomly(monster_position: &mut Position) {
+pub(crate) fn move_randomly(monster_position: &mut Position, rng: &mut GameRng) {
     let mut velocity_x = 0;
     let mut velocity_y = 0;
-    let mut rng = rand::rng();
-    let random_pick: u8 = rng.random_range(1..=4);
 
-    if random_pick == 1 {
-        velocity_x = 1;
-    } else if random_pick == 2 {
-        velocity_x = -1;
-    } else if random_pick == 3 {
-        velocity_y = 1;
-    } else if random_pick == 4 {
-        velocity_y = -1;
+    //pick any of the eight directions
+    while velocity_x == 0 && velocity_y == 0 {
+        velocity_x = rng.random_range(-1..=1);
+        velocity_y = rng.random_range(-1..=1);
     }
 
     monster_position.velocity_x = velocity_x;
@@ -120,14 +98,14 @@ pub(crate) fn move_randomly(monster_position: &mut Position) {
 pub(crate) fn get_all_players_nearby(
     player_positions: &PlayersPositions,
     monster_position: &Position,
+    aggro_radius: u32,
 ) -> HashMap<Uuid, Position> {
     let mut nearby_players = HashMap::new();
+    let aggro_radius = i32_from(aggro_radius);
     let monster_position_x = i32_from(monster_position.pos_x);
     let monster_position_y = i32_from(monster_position.pos_y);
-    let monster_x_range = (monster_position_x - MONSTER_DISTANCE_ACTIVATION)
-        ..(monster_position_x + MONSTER_DISTANCE_ACTIVATION);
-    let monster_y_range = (monster_position_y - MONSTER_DISTANCE_ACTIVATION)
-        ..(monster_position_y + MONSTER_DISTANCE_ACTIVATION);
+    let monster_x_range = (monster_position_x - aggro_radius)..(monster_position_x + aggro_radius);
+    let monster_y_range = (monster_position_y - aggro_radius)..(monster_position_y + aggro_radius);
 
     let identified_players = player_positions
         .par_iter()
@@ -144,3 +122,22 @@ pub(crate) fn get_all_players_nearby(
     }
     nearby_players
 }
+
+//only monsters from factions the searching monster attacks on sight
+pub(crate) fn get_all_hostile_monsters_nearby(
+    monsters_positions: &MonstersPositions,
+    monster: &MonsterDetails,
+    monster_position: &Position,
+    aggro_radius: u32,
+) -> HashMap<Uuid, Position> {
+    let hostile_monsters: HashMap<Uuid, Position> = monsters_positions
+        .iter()
+        .filter(|(monster_id, (position, faction))| {
+            **monster_id != monster.id
+                && position.current_map == monster_position.current_map
+                && monster.faction.is_hostile_to(faction)
+        })
+        .map(|(monster_id, (position, _))| (*monster_id, position.clone()))
+        .collect();
+    get_all_players_nearby(&hostile_monsters, monster_position, aggro_radius)
+}
@@ -0,0 +1,286 @@
+use crate::consts::{
+    PATHFINDING_DESTINATION_TOLERANCE, PATHFINDING_ENTITY_AVOIDANCE_DISTANCE,
+    PATHFINDING_MAX_SEARCH_NODES,
+};
+use crate::game::map::state;
+use crate::game::map::state::{AllMapStates, MapState};
+use crate::game::map::tiles;
+use crate::game::monsters::movement;
+use crate::game::rng::GameRng;
+use rustyhack_lib::background_map::{AllMaps, BackgroundMap};
+use rustyhack_lib::ecs::components::{EntityType, Position};
+use rustyhack_lib::utils::math::{i32_from, u32_from};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use uuid::Uuid;
+
+pub(crate) type MonsterPaths = HashMap<Uuid, MonsterPath>;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MonsterPath {
+    pub(crate) map: String,
+    pub(crate) destination: (u32, u32),
+    //next step first, ending at the destination
+    pub(crate) steps: Vec<(u32, u32)>,
+}
+
+impl MonsterPath {
+    //a target that has only moved a tile or so keeps the cached path, which is extended to follow it
+    pub(crate) fn is_valid_for(
+        &self,
+        map: &str,
+        start: (u32, u32),
+        destination: (u32, u32),
+        current_map: &BackgroundMap,
+        map_state: &MapState,
+    ) -> bool {
+        self.map == map
+            && get_distance(self.destination, destination) <= PATHFINDING_DESTINATION_TOLERANCE
+            && (self.destination == destination
+                || !tiles::is_squeezing_between_walls(current_map, self.destination, destination))
+            && self.steps.first().is_some_and(|next_step| {
+                get_distance(start, *next_step) == 1
+                    && !tiles::is_squeezing_between_walls(current_map, start, *next_step)
+                    && (*next_step == destination
+                        || is_walkable(current_map, map_state, start, *next_step))
+            })
+    }
+
+    fn follow_destination(&mut self, destination: (u32, u32)) {
+        if self.destination == destination {
+            return;
+        }
+        //drop any steps that already lead past the new destination
+        if let Some(index) = self.steps.iter().position(|step| *step == destination) {
+            self.steps.truncate(index + 1);
+        } else {
+            self.steps.push(destination);
+        }
+        self.destination = destination;
+    }
+}
+
+//A* search over the map tiles, returning the steps to take excluding the start position
+pub(crate) fn find_path(
+    current_map: &BackgroundMap,
+    map_state: &MapState,
+    start: (u32, u32),
+    destination: (u32, u32),
+) -> Option<Vec<(u32, u32)>> {
+    if start == destination {
+        return None;
+    }
+    let mut open_set = BinaryHeap::new();
+    let mut came_from: HashMap<(u32, u32), (u32, u32)> = HashMap::new();
+    let mut cost_so_far: HashMap<(u32, u32), u32> = HashMap::new();
+    open_set.push(Reverse((get_distance(start, destination), start)));
+    cost_so_far.insert(start, 0);
+
+    let mut searched_nodes = 0;
+    while let Some(Reverse((_estimated_cost, current))) = open_set.pop() {
+        if current == destination {
+            return Some(reconstruct_path(&came_from, start, destination));
+        }
+        searched_nodes += 1;
+        if searched_nodes > PATHFINDING_MAX_SEARCH_NODES {
+            debug!("Gave up searching for path from {start:?} to {destination:?}.");
+            return None;
+        }
+        let current_cost = cost_so_far[&current];
+        for neighbour in get_neighbours(current) {
+            //the destination is usually occupied by the target, so is always allowed
+            if (neighbour != destination && !is_walkable(current_map, map_state, start, neighbour))
+                || tiles::is_squeezing_between_walls(current_map, current, neighbour)
+            {
+                continue;
+            }
+            let new_cost = current_cost + 1;
+            if cost_so_far
+                .get(&neighbour)
+                .is_none_or(|existing_cost| new_cost < *existing_cost)
+            {
+                cost_so_far.insert(neighbour, new_cost);
+                came_from.insert(neighbour, current);
+                open_set.push(Reverse((
+                    new_cost + get_distance(neighbour, destination),
+                    neighbour,
+                )));
+            }
+        }
+    }
+    None
+}
+
+fn reconstruct_path(
+    came_from: &HashMap<(u32, u32), (u32, u32)>,
+    start: (u32, u32),
+    destination: (u32, u32),
+) -> Vec<(u32, u32)> {
+    let mut steps = vec![destination];
+    let mut current = destination;
+    while let Some(previous) = came_from.get(&current) {
+        if *previous == start {
+            break;
+        }
+        steps.push(*previous);
+        current = *previous;
+    }
+    steps.reverse();
+    steps
+}
+
+fn get_neighbours(position: (u32, u32)) -> Vec<(u32, u32)> {
+    let (x, y) = (i32_from(position.0), i32_from(position.1));
+    let mut neighbours = Vec::with_capacity(8);
+    for velocity_y in -1..=1 {
+        for velocity_x in -1..=1 {
+            if (velocity_x != 0 || velocity_y != 0) && x + velocity_x >= 0 && y + velocity_y >= 0 {
+                neighbours.push((u32_from(x + velocity_x), u32_from(y + velocity_y)));
+            }
+        }
+    }
+    neighbours
+}
+
+fn is_walkable(
+    current_map: &BackgroundMap,
+    map_state: &MapState,
+    start: (u32, u32),
+    position: (u32, u32),
+) -> bool {
+    let (x, y) = position;
+    if !current_map.is_within_boundary(y, x)
+        || !tiles::monster_can_walk_on_tile(current_map.get_tile_at(y, x))
+    {
+        return false;
+    }
+    //only avoid other monsters close by, ones further away will likely have moved on by then
+    get_distance(start, position) > PATHFINDING_ENTITY_AVOIDANCE_DISTANCE
+        || !is_occupied_by_monster(map_state, position)
+}
+
+fn is_occupied_by_monster(map_state: &MapState, position: (u32, u32)) -> bool {
+    map_state
+        .get((position.1 as usize, position.0 as usize))
+        .is_some_and(|entities| {
+            entities.iter().any(|entity| {
+                matches!(entity, EntityType::Monster(monster) if monster.display_details.collidable)
+            })
+        })
+}
+
+//diagonal moves cost the same as orthogonal ones, so distance is the larger of the two axes
+pub(crate) fn get_distance(from: (u32, u32), to: (u32, u32)) -> u32 {
+    from.0.abs_diff(to.0).max(from.1.abs_diff(to.1))
+}
+
+//sets the monster velocity to the next step on a cached or newly found path,
+//falling back to simple movement towards the target if no path can be found
+pub(crate) fn move_along_path(
+    monster_id: Uuid,
+    monster_position: &mut Position,
+    target_position: &Position,
+    all_maps: &AllMaps,
+    all_map_states: &AllMapStates,
+    monster_paths: &mut MonsterPaths,
+    rng: &mut GameRng,
+) {
+    let (Some(current_map), Some(map_state)) = (
+        all_maps.get(&monster_position.current_map),
+        all_map_states.get(&monster_position.current_map),
+    ) else {
+        movement::move_towards_target(monster_position, target_position, rng);
+        return;
+    };
+    let start = (monster_position.pos_x, monster_position.pos_y);
+    let destination = (target_position.pos_x, target_position.pos_y);
+
+    let cached_path_is_valid = monster_paths.get(&monster_id).is_some_and(|monster_path| {
+        monster_path.is_valid_for(
+            &monster_position.current_map,
+            start,
+            destination,
+            current_map,
+            map_state,
+        )
+    });
+    if !cached_path_is_valid {
+        let Some(steps) = find_path(current_map, map_state, start, destination) else {
+            monster_paths.remove(&monster_id);
+            movement::move_towards_target(monster_position, target_position, rng);
+            return;
+        };
+        monster_paths.insert(
+            monster_id,
+            MonsterPath {
+                map: monster_position.current_map.clone(),
+                destination,
+                steps,
+            },
+        );
+    }
+
+    let Some(monster_path) = monster_paths.get_mut(&monster_id) else {
+        monster_position.velocity_x = 0;
+        monster_position.velocity_y = 0;
+        return;
+    };
+    monster_path.follow_destination(destination);
+    let next_step = monster_path.steps.remove(0);
+    monster_position.velocity_x = i32_from(next_step.0) - i32_from(start.0);
+    monster_position.velocity_y = i32_from(next_step.1) - i32_from(start.1);
+    if monster_path.steps.is_empty() {
+        monster_paths.remove(&monster_id);
+    }
+}
+
+//sets the monster velocity to whichever free neighbouring tile takes it furthest from the target,
+//turning to fight if there is nowhere further away to go
+pub(crate) fn move_away_from_target(
+    monster_position: &mut Position,
+    target_position: &Position,
+    all_maps: &AllMaps,
+    all_map_states: &AllMapStates,
+    rng: &mut GameRng,
+) {
+    let start = (monster_position.pos_x, monster_position.pos_y);
+    let target = (target_position.pos_x, target_position.pos_y);
+    let current_distance = get_distance(start, target);
+    let furthest_step = match (
+        all_maps.get(&monster_position.current_map),
+        all_map_states.get(&monster_position.current_map),
+    ) {
+        (Some(current_map), Some(map_state)) => get_neighbours(start)
+            .into_iter()
+            .filter(|neighbour| {
+                is_walkable(current_map, map_state, start, *neighbour)
+                    && !tiles::is_squeezing_between_walls(current_map, start, *neighbour)
+                    && get_distance(*neighbour, target) > current_distance
+            })
+            .max_by_key(|neighbour| get_distance(*neighbour, target)),
+        _ => None,
+    };
+    match furthest_step {
+        Some(next_step) => {
+            monster_position.velocity_x = i32_from(next_step.0) - i32_from(start.0);
+            monster_position.velocity_y = i32_from(next_step.1) - i32_from(start.1);
+        }
+        None => movement::move_towards_target(monster_position, target_position, rng),
+    }
+}
+
+//neighbouring tiles a monster could be placed on without colliding with anything
+pub(crate) fn get_free_neighbours(
+    current_map: &BackgroundMap,
+    map_state: &MapState,
+    position: (u32, u32),
+) -> Vec<(u32, u32)> {
+    get_neighbours(position)
+        .into_iter()
+        .filter(|neighbour| {
+            is_walkable(current_map, map_state, position, *neighbour)
+                && !tiles::is_squeezing_between_walls(current_map, position, *neighbour)
+                && !state::is_colliding_with_entity(neighbour.0, neighbour.1, map_state).0
+        })
+        .collect()
+}
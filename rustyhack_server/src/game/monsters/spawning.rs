@@ -1,136 +1,207 @@
 use crate::consts;
-use crate::game::map::spawns::{AllSpawnCounts, AllSpawnsMap, PositionWithoutMap};
+use crate::game::map::spawns::{
+    AllSpawnCounts, AllSpawnTimers, AllSpawnsMap, MonsterSpawnPositions,
+};
+use crate::game::map::{state, tiles};
+use crate::game::players::PlayersPositions;
+use crate::game::rng::{self, GameRng};
+use crate::game::time_of_day::{self, TimeOfDay};
 use legion::World;
 use legion::systems::CommandBuffer;
 use rand::RngExt;
 use rand::prelude::IndexedRandom;
-use rustyhack_lib::ecs::components::{DisplayDetails, Inventory, MonsterDetails, Position, Stats};
-use rustyhack_lib::ecs::monster::AllMonsterDefinitions;
-use std::collections::HashMap;
+use rustyhack_lib::background_map::{AllMaps, BackgroundMap, field_of_view};
+use rustyhack_lib::consts::DEFAULT_PLAYER_VIEW_DISTANCE;
+use rustyhack_lib::ecs::components::{
+    DisplayDetails, Inventory, MonsterDetails, Position, Stats, StatusEffects,
+};
+use rustyhack_lib::ecs::monster::{AllMonsterDefinitions, EliteAffix, Monster};
+use std::collections::{HashMap, HashSet};
 use std::process;
 use uuid::Uuid;
 
-pub(crate) fn count_monsters_needing_respawn(
-    current_monsters_count: &AllSpawnCounts,
-    default_spawn_counts: &AllSpawnCounts,
-) -> AllSpawnCounts {
-    //get diff between default spawns and current alive monsters
-    let mut monsters_needing_respawn: AllSpawnCounts = HashMap::new();
-    for (map, spawns) in default_spawn_counts {
-        let mut map_monsters_needing_respawn: HashMap<String, u32> = HashMap::new();
-        for (monster, count) in spawns {
-            let needing_respawn_count = count
-                - current_monsters_count
-                    .get(map)
-                    .unwrap_or(&HashMap::new())
-                    .get(monster)
-                    .unwrap_or(&0);
-            map_monsters_needing_respawn.insert(monster.clone(), needing_respawn_count);
-        }
-        monsters_needing_respawn.insert(map.clone(), map_monsters_needing_respawn);
-    }
-    debug!("Monsters needing respawn are: {monsters_needing_respawn:?}");
-    monsters_needing_respawn
-}
-
 pub(crate) fn count_alive_monsters(
     mut current_monsters_count: AllSpawnCounts,
     monster: &MonsterDetails,
     position: &Position,
-) -> HashMap<String, HashMap<String, u32>> {
-    if current_monsters_count.contains_key(&position.current_map) {
-        let mut map_monster_count: HashMap<String, u32> = current_monsters_count
+    all_spawns_map: &AllSpawnsMap,
+) -> AllSpawnCounts {
+    //monsters saved before spawn entries were tracked count towards the first entry for their type
+    let Some(spawn_index) = monster.spawn_index.or_else(|| {
+        all_spawns_map
             .get(&position.current_map)
-            .unwrap()
-            .clone();
-        if map_monster_count.contains_key(&monster.monster_type) {
-            let mut count: u32 = *map_monster_count.get(&monster.monster_type).unwrap();
-            count += 1;
-            map_monster_count.insert(monster.monster_type.clone(), count);
-            current_monsters_count.insert(position.current_map.clone(), map_monster_count.clone());
-        } else {
-            map_monster_count.insert(monster.monster_type.clone(), 1);
-            current_monsters_count.insert(position.current_map.clone(), map_monster_count.clone());
-        }
-    } else {
-        let mut monster_count: HashMap<String, u32> = HashMap::new();
-        monster_count.insert(monster.monster_type.clone(), 1);
-        current_monsters_count.insert(position.current_map.clone(), monster_count);
-    }
+            .and_then(|spawns| {
+                spawns
+                    .monsters
+                    .iter()
+                    .position(|monster_spawn| monster_spawn.monster_type == monster.monster_type)
+            })
+    }) else {
+        return current_monsters_count;
+    };
+    *current_monsters_count
+        .entry(position.current_map.clone())
+        .or_default()
+        .entry(spawn_index)
+        .or_insert(0) += 1;
     debug!("Current monsters alive are: {current_monsters_count:?}");
     current_monsters_count
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn respawn_monsters(
-    monsters_needing_respawn: &AllSpawnCounts,
+    current_monsters_count: &AllSpawnCounts,
     all_monster_definitions: &AllMonsterDefinitions,
     all_spawns_map: &AllSpawnsMap,
+    all_maps: &AllMaps,
+    blocked_spawn_tiles: &mut HashMap<String, HashSet<(u32, u32)>>,
+    spawn_timers: &mut AllSpawnTimers,
+    rng: &mut GameRng,
     commands: &mut CommandBuffer,
 ) {
-    for (map, spawns) in monsters_needing_respawn {
-        for (monster_type, count) in spawns {
-            //only spawn at most 1 of each monster_type per map per tick
-            if should_respawn_this_tick() && count > &0 {
-                spawn_single_monster(
-                    all_monster_definitions,
-                    all_spawns_map,
-                    map,
-                    monster_type,
-                    commands,
-                );
+    let time_of_day = time_of_day::get_current_time_of_day();
+    for (map, spawns) in all_spawns_map {
+        let current_map = state::get_current_map(all_maps, map);
+        let map_blocked_spawn_tiles = blocked_spawn_tiles.entry(map.clone()).or_default();
+        let map_spawn_timers = spawn_timers.entry(map.clone()).or_default();
+        for (spawn_index, monster_spawn) in spawns.monsters.iter().enumerate() {
+            let alive_count = current_monsters_count
+                .get(map)
+                .and_then(|map_counts| map_counts.get(&spawn_index))
+                .copied()
+                .unwrap_or(0);
+            let spawn_timer = map_spawn_timers.entry(spawn_index).or_insert(0);
+            if alive_count >= monster_spawn.max_population() {
+                *spawn_timer = 0;
+                continue;
+            }
+            if !can_spawn_at(monster_spawn, time_of_day) {
+                continue;
+            }
+            *spawn_timer += 1;
+            //below the min population monsters are replaced without waiting for the timer
+            if alive_count >= monster_spawn.min_population()
+                && *spawn_timer < monster_spawn.respawn_ticks
+            {
+                continue;
+            }
+            if spawn_single_monster(
+                get_monster_definition(all_monster_definitions, monster_spawn),
+                (spawn_index, monster_spawn),
+                map,
+                current_map,
+                map_blocked_spawn_tiles,
+                rng,
+                commands,
+            ) {
+                *spawn_timer = 0;
             }
         }
     }
 }
 
-fn should_respawn_this_tick() -> bool {
-    //random chance for respawning each chick
-    let mut rng = rand::rng();
-    consts::TICK_SPAWN_CHANCE_PERCENTAGE >= rng.random_range(0..=101)
+//monsters can't spawn on top of other monsters or players, or anywhere a player can see
+pub(crate) fn add_tiles_seen_by_players(
+    blocked_spawn_tiles: &mut HashMap<String, HashSet<(u32, u32)>>,
+    players_positions: &PlayersPositions,
+    all_maps: &AllMaps,
+) {
+    for position in players_positions.values() {
+        let current_map = state::get_current_map(all_maps, &position.current_map);
+        let map_blocked_spawn_tiles = blocked_spawn_tiles
+            .entry(position.current_map.clone())
+            .or_default();
+        map_blocked_spawn_tiles.insert((position.pos_x, position.pos_y));
+        map_blocked_spawn_tiles.extend(field_of_view::get_visible_tiles(
+            current_map,
+            position.pos_x,
+            position.pos_y,
+            DEFAULT_PLAYER_VIEW_DISTANCE,
+        ));
+    }
+}
+
+fn can_spawn_at(monster_spawn: &MonsterSpawnPositions, time_of_day: TimeOfDay) -> bool {
+    monster_spawn
+        .time_of_day
+        .is_none_or(|spawn_time_of_day| spawn_time_of_day == time_of_day)
+}
+
+fn choose_spawn_tile(
+    monster_spawn: &MonsterSpawnPositions,
+    current_map: &BackgroundMap,
+    blocked_spawn_tiles: &HashSet<(u32, u32)>,
+    rng: &mut GameRng,
+) -> Option<(u32, u32)> {
+    let free_spawn_tiles: Vec<(u32, u32)> = monster_spawn
+        .get_all_spawn_tiles()
+        .into_iter()
+        .filter(|(x, y)| {
+            current_map.is_within_boundary(*y, *x)
+                && tiles::monster_can_walk_on_tile(current_map.get_tile_at(*y, *x))
+                && !blocked_spawn_tiles.contains(&(*x, *y))
+        })
+        .collect();
+    free_spawn_tiles.choose(rng).copied()
 }
 
 pub(crate) fn spawn_initial_monsters(
     world: &mut World,
     all_monster_definitions: &AllMonsterDefinitions,
     all_spawns_map: &AllSpawnsMap,
+    all_maps: &AllMaps,
+    rng: &mut GameRng,
 ) {
     info!("Spawning initial monsters.");
-    let mut monsters_vec: Vec<(MonsterDetails, DisplayDetails, Position, Stats, Inventory)> =
-        vec![];
+    let time_of_day = time_of_day::get_current_time_of_day();
+    let mut monsters_vec: Vec<(
+        MonsterDetails,
+        DisplayDetails,
+        Position,
+        Stats,
+        Inventory,
+        StatusEffects,
+    )> = vec![];
     for (map, spawns) in all_spawns_map {
-        for monster in &spawns.monsters {
-            let mut current_monster = all_monster_definitions
-                .get(&monster.monster_type)
-                .unwrap_or_else(|| {
-                    error!(
-                        "Monster {} missing from all_monster_definitions.",
-                        &monster.monster_type,
+        let current_map = state::get_current_map(all_maps, map);
+        let mut blocked_spawn_tiles: HashSet<(u32, u32)> = HashSet::new();
+        for (spawn_index, monster_spawn) in spawns
+            .monsters
+            .iter()
+            .enumerate()
+            .filter(|(_, monster_spawn)| can_spawn_at(monster_spawn, time_of_day))
+        {
+            let monster_definition = get_monster_definition(all_monster_definitions, monster_spawn);
+            for _ in 0..monster_spawn.max_population() {
+                let Some((x, y)) =
+                    choose_spawn_tile(monster_spawn, current_map, &blocked_spawn_tiles, rng)
+                else {
+                    warn!(
+                        "No free spawn tiles left for monster {} on map {map}.",
+                        monster_spawn.monster_type
                     );
-                    process::exit(1);
-                })
-                .clone();
-            for spawn_position in &monster.spawn_positions {
-                let position = Position {
-                    update_available: false,
-                    pos_x: spawn_position.x,
-                    pos_y: spawn_position.y,
-                    current_map: map.clone(),
-                    velocity_x: 0,
-                    velocity_y: 0,
+                    break;
                 };
-                current_monster.monster_details.id = Uuid::new_v4();
-                current_monster.monster_details.spawn_position = position.clone();
-                current_monster.position = position;
+                blocked_spawn_tiles.insert((x, y));
+                let current_monster = create_monster(
+                    monster_definition,
+                    (spawn_index, monster_spawn),
+                    new_spawn_position(map, x, y),
+                    rng,
+                );
                 info!(
-                    "Spawned monster {} at position {:?}",
-                    current_monster.monster_details.monster_type, current_monster.position
+                    "Spawned monster {} level {} at position {:?}",
+                    current_monster.monster_details.display_name(),
+                    current_monster.stats.level,
+                    current_monster.position
                 );
                 monsters_vec.push((
-                    current_monster.monster_details.clone(),
+                    current_monster.monster_details,
                     current_monster.display_details,
                     current_monster.position,
                     current_monster.stats,
-                    current_monster.inventory.clone(),
+                    current_monster.inventory,
+                    StatusEffects::default(),
                 ));
             }
         }
@@ -139,51 +210,184 @@ pub(crate) fn spawn_initial_monsters(
 }
 
 fn spawn_single_monster(
-    all_monster_definitions: &AllMonsterDefinitions,
-    all_spawns_map: &AllSpawnsMap,
+    monster_definition: &Monster,
+    (spawn_index, monster_spawn): (usize, &MonsterSpawnPositions),
     map: &str,
-    monster_type: &str,
+    current_map: &BackgroundMap,
+    blocked_spawn_tiles: &mut HashSet<(u32, u32)>,
+    rng: &mut GameRng,
     commands: &mut CommandBuffer,
-) {
+) -> bool {
     info!("Spawning single monster.");
-    let mut current_monster = all_monster_definitions
-        .get(monster_type)
+    let Some((x, y)) = choose_spawn_tile(monster_spawn, current_map, blocked_spawn_tiles, rng)
+    else {
+        debug!(
+            "No free spawn tiles for monster {} on map {map}, trying again next tick.",
+            monster_spawn.monster_type
+        );
+        return false;
+    };
+    blocked_spawn_tiles.insert((x, y));
+    let current_monster = create_monster(
+        monster_definition,
+        (spawn_index, monster_spawn),
+        new_spawn_position(map, x, y),
+        rng,
+    );
+    info!(
+        "Spawned {} level {} at position: ({} {})",
+        current_monster.monster_details.display_name(),
+        current_monster.stats.level,
+        x,
+        y
+    );
+    commands.push((
+        current_monster.monster_details,
+        current_monster.display_details,
+        current_monster.position,
+        current_monster.stats,
+        current_monster.inventory,
+        StatusEffects::default(),
+    ));
+    true
+}
+
+fn new_spawn_position(map: &str, x: u32, y: u32) -> Position {
+    Position {
+        update_available: false,
+        pos_x: x,
+        pos_y: y,
+        current_map: map.to_string(),
+        velocity_x: 0,
+        velocity_y: 0,
+    }
+}
+
+fn get_monster_definition<'a>(
+    all_monster_definitions: &'a AllMonsterDefinitions,
+    monster_spawn_positions: &MonsterSpawnPositions,
+) -> &'a Monster {
+    all_monster_definitions
+        .get(&monster_spawn_positions.monster_type)
         .unwrap_or_else(|| {
-            error!("Monster {monster_type} missing from all_monster_definitions.");
+            error!(
+                "Monster {} missing from all_monster_definitions.",
+                &monster_spawn_positions.monster_type,
+            );
             process::exit(1);
         })
-        .clone();
-    let all_spawn_positions = all_spawns_map.get(map).unwrap();
-    let mut random_spawn_position: PositionWithoutMap;
-    for monster_spawn_positions in &all_spawn_positions.monsters {
-        if monster_spawn_positions.monster_type.eq(monster_type) {
-            random_spawn_position = *monster_spawn_positions
-                .spawn_positions
-                .choose(&mut rand::rng())
-                .unwrap();
+}
 
-            let position = Position {
-                update_available: false,
-                pos_x: random_spawn_position.x,
-                pos_y: random_spawn_position.y,
-                current_map: map.to_string(),
-                velocity_x: 0,
-                velocity_y: 0,
-            };
-            current_monster.monster_details.id = Uuid::new_v4();
-            current_monster.monster_details.spawn_position = position.clone();
-            current_monster.position = position.clone();
-            commands.push((
-                current_monster.monster_details.clone(),
-                current_monster.display_details,
-                current_monster.position,
-                current_monster.stats,
-                current_monster.inventory.clone(),
-            ));
-            info!(
-                "Spawned {} at position: ({} {})",
-                current_monster.monster_details.monster_type, position.pos_x, position.pos_y
-            );
+fn create_monster(
+    monster_definition: &Monster,
+    (spawn_index, monster_spawn_positions): (usize, &MonsterSpawnPositions),
+    position: Position,
+    rng: &mut GameRng,
+) -> Monster {
+    let mut current_monster = monster_definition.clone();
+    current_monster.monster_details.id = rng::new_uuid(rng);
+    current_monster.monster_details.spawn_position = position.clone();
+    current_monster.monster_details.spawn_index = Some(spawn_index);
+    current_monster.position = position;
+    //level ranges are checked when spawns are loaded, but never panic on a reversed range
+    if let Some(levels) = monster_spawn_positions.levels {
+        let level = rng.random_range(levels.min.min(levels.max)..=levels.max.max(levels.min));
+        scale_monster_to_level(&mut current_monster, level);
+    }
+    //bosses are already unique enough
+    if current_monster.monster_details.boss.is_none() {
+        let elite_affixes =
+            roll_elite_affixes(monster_spawn_positions.elite_chance_percentage, rng);
+        for affix in &elite_affixes {
+            apply_elite_affix(&mut current_monster, *affix);
+        }
+        if let Some(affix) = elite_affixes.first() {
+            current_monster.display_details.colour = affix.colour();
         }
+        current_monster.monster_details.elite_affixes = elite_affixes;
     }
+    current_monster
+}
+
+fn scale_monster_to_level(monster: &mut Monster, level: u32) {
+    //each level away from the definition changes stats by a fixed fraction of the base stats
+    let mut multiplier = 1.0;
+    for _ in monster.stats.level..level {
+        multiplier += consts::MONSTER_STAT_SCALING_PER_LEVEL;
+    }
+    for _ in level..monster.stats.level {
+        multiplier -= consts::MONSTER_STAT_SCALING_PER_LEVEL;
+    }
+    let multiplier = multiplier.max(consts::MONSTER_STAT_SCALING_PER_LEVEL);
+    monster.stats.max_hp *= multiplier;
+    monster.stats.current_hp = monster.stats.max_hp;
+    monster.stats.str *= multiplier;
+    monster.stats.dex *= multiplier;
+    monster.stats.con *= multiplier;
+    monster.stats.level = level;
+}
+
+fn roll_elite_affixes(elite_chance_percentage: u32, rng: &mut GameRng) -> Vec<EliteAffix> {
+    if elite_chance_percentage <= rng.random_range(0..100) {
+        return vec![];
+    }
+    let affix_count = if consts::CHAMPION_CHANCE_PERCENTAGE > rng.random_range(0..100) {
+        2
+    } else {
+        1
+    };
+    [EliteAffix::Hardy, EliteAffix::Swift, EliteAffix::Fiery]
+        .sample(rng, affix_count)
+        .copied()
+        .collect()
+}
+
+fn apply_elite_affix(monster: &mut Monster, affix: EliteAffix) {
+    match affix {
+        EliteAffix::Hardy => {
+            monster.stats.max_hp *= consts::HARDY_HP_MULTIPLIER;
+            monster.stats.current_hp = monster.stats.max_hp;
+        }
+        EliteAffix::Swift => {
+            monster.stats.dex *= consts::SWIFT_DEX_MULTIPLIER;
+            monster.inventory.equipped.weapon.speed *= consts::SWIFT_WEAPON_SPEED_MULTIPLIER;
+        }
+        EliteAffix::Fiery => {
+            let weapon = &mut monster.inventory.equipped.weapon;
+            weapon.damage_range = weapon.damage_range.start + consts::FIERY_DAMAGE_BONUS
+                ..weapon.damage_range.end + consts::FIERY_DAMAGE_BONUS;
+        }
+    }
+    //exp is based on level, so elites are worth more
+    monster.stats.level += consts::ELITE_LEVEL_BONUS_PER_AFFIX;
+}
+
+pub(crate) fn spawn_summoned_monster(
+    all_monster_definitions: &AllMonsterDefinitions,
+    monster_type: &str,
+    summoner_id: Uuid,
+    position: Position,
+    rng: &mut GameRng,
+    commands: &mut CommandBuffer,
+) -> Option<Uuid> {
+    let Some(monster_definition) = all_monster_definitions.get(monster_type) else {
+        warn!("Tried to summon monster {monster_type} missing from all_monster_definitions.");
+        return None;
+    };
+    let mut current_monster = monster_definition.clone();
+    current_monster.monster_details.id = rng::new_uuid(rng);
+    current_monster.monster_details.spawn_position = position.clone();
+    //summoned monsters don't count towards the map spawn counts
+    current_monster.monster_details.summoned_by = Some(summoner_id);
+    current_monster.position = position;
+    let summon_id = current_monster.monster_details.id;
+    commands.push((
+        current_monster.monster_details,
+        current_monster.display_details,
+        current_monster.position,
+        current_monster.stats,
+        current_monster.inventory,
+        StatusEffects::default(),
+    ));
+    Some(summon_id)
 }
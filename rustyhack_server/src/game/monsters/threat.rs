@@ -0,0 +1,136 @@
+use crate::consts::{
+    THREAT_DECAY_MULTIPLIER, THREAT_MINIMUM, THREAT_PER_ATTACK, THREAT_PER_DAMAGE,
+    THREAT_PER_PROXIMITY, THREAT_TARGET_SWITCH_MULTIPLIER,
+};
+use crate::game::monsters::MonstersPositions;
+use crate::game::monsters::pathfinding;
+use crate::game::players::PlayersPositions;
+use rustyhack_lib::ecs::components::Position;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+//threat each player or monster has generated against each monster, keyed by monster id then attacker id
+pub(crate) type ThreatTables = HashMap<Uuid, ThreatTable>;
+pub(crate) type ThreatTable = HashMap<Uuid, f32>;
+
+pub(crate) fn add_damage_threat(
+    threat_tables: &mut ThreatTables,
+    monster_id: Uuid,
+    attacker_id: Uuid,
+    damage: f32,
+) {
+    *threat_tables
+        .entry(monster_id)
+        .or_default()
+        .entry(attacker_id)
+        .or_default() += THREAT_PER_ATTACK + damage * THREAT_PER_DAMAGE;
+}
+
+//forgets targets who have left the map, logged out or died, and starts tracking hostile targets
+//that have just come close by, the threat itself only changes on the server tick
+pub(crate) fn refresh_threat_table(
+    threat_table: &mut ThreatTable,
+    players_positions: &PlayersPositions,
+    monsters_positions: &MonstersPositions,
+    nearby_targets: &HashMap<Uuid, Position>,
+    monster_position: &Position,
+    include_proximity: bool,
+) {
+    threat_table.retain(|target_id, _| {
+        is_target_on_map(
+            *target_id,
+            players_positions,
+            monsters_positions,
+            monster_position,
+        )
+    });
+    if include_proximity {
+        for (target_id, target_position) in nearby_targets {
+            threat_table
+                .entry(*target_id)
+                .or_insert_with(|| get_proximity_threat(monster_position, target_position));
+        }
+    }
+}
+
+//decays existing threat and adds threat for hostile targets standing close by,
+//run once per server tick so it doesn't depend on how often the monster gets to act
+pub(crate) fn update_threat_table(
+    threat_table: &mut ThreatTable,
+    players_positions: &PlayersPositions,
+    monsters_positions: &MonstersPositions,
+    nearby_targets: &HashMap<Uuid, Position>,
+    monster_position: &Position,
+    include_proximity: bool,
+) {
+    threat_table.retain(|target_id, threat| {
+        *threat *= THREAT_DECAY_MULTIPLIER;
+        *threat >= THREAT_MINIMUM
+            && is_target_on_map(
+                *target_id,
+                players_positions,
+                monsters_positions,
+                monster_position,
+            )
+    });
+    if include_proximity {
+        for (target_id, target_position) in nearby_targets {
+            *threat_table.entry(*target_id).or_default() +=
+                get_proximity_threat(monster_position, target_position);
+        }
+    }
+}
+
+fn is_target_on_map(
+    target_id: Uuid,
+    players_positions: &PlayersPositions,
+    monsters_positions: &MonstersPositions,
+    monster_position: &Position,
+) -> bool {
+    get_target_position(target_id, players_positions, monsters_positions)
+        .is_some_and(|position| position.current_map == monster_position.current_map)
+}
+
+fn get_proximity_threat(monster_position: &Position, target_position: &Position) -> f32 {
+    let distance = pathfinding::get_distance(
+        (monster_position.pos_x, monster_position.pos_y),
+        (target_position.pos_x, target_position.pos_y),
+    )
+    .max(1);
+    THREAT_PER_PROXIMITY / f32::from(u16::try_from(distance).unwrap_or(u16::MAX))
+}
+
+pub(crate) fn get_target_position<'a>(
+    target_id: Uuid,
+    players_positions: &'a PlayersPositions,
+    monsters_positions: &'a MonstersPositions,
+) -> Option<&'a Position> {
+    players_positions.get(&target_id).or_else(|| {
+        monsters_positions
+            .get(&target_id)
+            .map(|(position, _)| position)
+    })
+}
+
+//sticks with the current target unless another target has clearly generated more threat
+pub(crate) fn get_highest_threat_target(
+    threat_table: &ThreatTable,
+    current_target: Option<Uuid>,
+) -> Option<Uuid> {
+    let (highest_target_id, highest_threat) =
+        threat_table
+            .iter()
+            .max_by(|(id_a, threat_a), (id_b, threat_b)| {
+                //equal threat is settled by id rather than the unordered table
+                threat_a.total_cmp(threat_b).then_with(|| id_b.cmp(id_a))
+            })?;
+    match current_target.and_then(|target| threat_table.get(&target).map(|threat| (target, threat)))
+    {
+        Some((target, current_threat))
+            if *highest_threat < current_threat * THREAT_TARGET_SWITCH_MULTIPLIER =>
+        {
+            Some(target)
+        }
+        _ => Some(*highest_target_id),
+    }
+}
@@ -1,14 +1,22 @@
+use crate::game::abilities::PendingAbilities;
+use crate::game::combat::PendingRangedAttacks;
 use crate::game::ecs::queries;
 use crate::game::ecs::queries::{common_player, player_joined, player_left};
 use crate::game::map::exits::AllMapExits;
+use crate::game::map::state::AllMapStates;
+use crate::game::map::terrain::PendingTileChanges;
+use crate::game::rng::GameRng;
 use crossbeam_channel::{Receiver, Sender};
 use laminar::Packet;
-use legion::World;
+use legion::{Resources, World};
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::ecs::ability::AllAbilities;
 use rustyhack_lib::network::packets::PlayerRequest;
+use uuid::Uuid;
 
 pub(super) fn process_player_messages(
     world: &mut World,
-    all_map_exits: &AllMapExits,
+    resources: &Resources,
     channel_receiver: &Receiver<PlayerRequest>,
     sender: &Sender<Packet>,
 ) -> bool {
@@ -17,7 +25,7 @@ pub(super) fn process_player_messages(
         debug!("Player messages are present.");
         let received = channel_receiver.try_recv();
         if let Ok(received_message) = received {
-            match_received_message(received_message, world, all_map_exits, sender);
+            match_received_message(received_message, world, resources, sender);
             has_player_updates = true;
         } else {
             debug!("Player messages channel receiver is now empty.");
@@ -29,7 +37,7 @@ pub(super) fn process_player_messages(
 fn match_received_message(
     received_message: PlayerRequest,
     world: &mut World,
-    all_map_exits: &AllMapExits,
+    resources: &Resources,
     sender: &Sender<Packet>,
 ) {
     match received_message {
@@ -40,6 +48,9 @@ fn match_received_message(
             );
             player_joined::join_player(
                 world,
+                &resources.get::<AllMapExits>().unwrap(),
+                &resources.get::<AllAbilities>().unwrap(),
+                &resources.get::<AllMaps>().unwrap(),
                 &client_details.player_name,
                 client_details.client_addr,
                 sender,
@@ -57,7 +68,7 @@ fn match_received_message(
         PlayerRequest::ChangeMap(position_message) => {
             queries::change_map::change_map_request(
                 world,
-                all_map_exits,
+                &resources.get::<AllMapExits>().unwrap(),
                 &position_message,
                 sender,
             );
@@ -70,35 +81,68 @@ fn match_received_message(
                 sender,
             );
         }
+        PlayerRequest::Search(position_message) => {
+            queries::search::search_for_traps(
+                world,
+                &resources.get::<AllMaps>().unwrap(),
+                &position_message,
+                &mut resources.get_mut::<GameRng>().unwrap(),
+                sender,
+            );
+        }
+        PlayerRequest::BuildBarricade(build_details) => {
+            queries::build_barricade::build_barricade(
+                world,
+                &resources.get::<AllMaps>().unwrap(),
+                &mut resources.get_mut::<AllMapStates>().unwrap(),
+                &mut resources.get_mut::<PendingTileChanges>().unwrap(),
+                &build_details,
+                sender,
+            );
+        }
+        PlayerRequest::RangedAttack(ranged_attack_details) => {
+            queries::ranged_attack::queue_ranged_attack(
+                world,
+                &mut resources.get_mut::<PendingRangedAttacks>().unwrap(),
+                &ranged_attack_details,
+            );
+        }
+        PlayerRequest::UseAbility(ability_details) => {
+            queries::use_ability::queue_ability(
+                world,
+                &mut resources.get_mut::<PendingAbilities>().unwrap(),
+                &ability_details,
+            );
+        }
+        PlayerRequest::TogglePvp(player_name) => {
+            queries::toggle_pvp::toggle_pvp(world, &player_name, sender);
+        }
         PlayerRequest::PlayerLogout(client_details) => {
             info!(
                 "Player logout notification received for {} from: {}",
                 &client_details.player_name, &client_details.client_addr
             );
-            let (logged_out_player_id, logged_out_map) = player_left::set_player_logged_out(
+            let logged_out_player = player_left::set_player_logged_out(
                 world,
                 &client_details.client_addr,
                 &client_details.player_name,
             );
-            player_left::broadcast_player_logged_out(
-                world,
-                sender,
-                logged_out_player_id,
-                &logged_out_map,
-            );
+            broadcast_player_left(world, sender, logged_out_player);
         }
         PlayerRequest::Timeout(address) => {
-            let (logged_out_player_id, logged_out_map) =
-                player_left::set_player_disconnected(world, &address);
-            player_left::broadcast_player_logged_out(
-                world,
-                sender,
-                logged_out_player_id,
-                &logged_out_map,
-            );
+            let logged_out_player = player_left::set_player_disconnected(world, &address);
+            broadcast_player_left(world, sender, logged_out_player);
         }
         _ => {
             warn!("Didn't match any known message to process.");
         }
     }
 }
+
+fn broadcast_player_left(
+    world: &mut World,
+    sender: &Sender<Packet>,
+    (logged_out_player_id, logged_out_map): (Uuid, String),
+) {
+    player_left::broadcast_player_logged_out(world, sender, logged_out_player_id, &logged_out_map);
+}
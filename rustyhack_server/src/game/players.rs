@@ -1,7 +1,15 @@
+use crate::consts::EXPLORED_TILES_PER_PACKET;
+use crate::game::map::exits::MapExitPositions;
 use bincode::{config, encode_to_vec};
 use crossbeam_channel::Sender;
 use laminar::Packet;
-use rustyhack_lib::ecs::components::Position;
+use rustyhack_lib::background_map::AllMaps;
+use rustyhack_lib::background_map::lighting;
+use rustyhack_lib::background_map::tiles::Tile;
+use rustyhack_lib::consts::DEFAULT_PLAYER_VIEW_DISTANCE;
+use rustyhack_lib::ecs::ability::AllAbilities;
+use rustyhack_lib::ecs::components::{ExploredMaps, Inventory, PlayerDetails, Position};
+use rustyhack_lib::ecs::item::get_carried_light_radius;
 use rustyhack_lib::ecs::player::Player;
 use rustyhack_lib::network::packets::ServerMessage;
 use std::collections::HashMap;
@@ -10,9 +18,17 @@ use uuid::Uuid;
 
 pub(super) type PlayersPositions = HashMap<Uuid, Position>;
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum DeathPenaltyMode {
+    #[default]
+    ExpLoss,
+    //carried items are also left behind in a corpse at the death location
+    Corpse,
+}
+
 pub(super) fn send_player_joined_response(player: &Player, sender: &Sender<Packet>) {
     let response = encode_to_vec(
-        ServerMessage::PlayerJoined(player.clone()),
+        ServerMessage::PlayerJoined(Box::new(player.clone())),
         config::standard(),
     )
     .unwrap_or_else(|err| {
@@ -28,3 +44,173 @@ pub(super) fn send_player_joined_response(player: &Player, sender: &Sender<Packe
         sender,
     );
 }
+
+//adds any tiles the player can currently see to their explored tiles, and sends the new ones
+pub(super) fn explore_visible_tiles(
+    player_details: &PlayerDetails,
+    position: &Position,
+    inventory: &Inventory,
+    explored_maps: &mut ExploredMaps,
+    all_maps: &AllMaps,
+    sender: &Sender<Packet>,
+) {
+    if player_details.currently_online
+        && let Some(current_map) = all_maps.get(&position.current_map)
+    {
+        let explored_tiles = explored_maps
+            .maps
+            .entry(position.current_map.clone())
+            .or_default();
+        let newly_explored_tiles: Vec<(u32, u32)> = lighting::get_lit_visible_tiles(
+            current_map,
+            position.pos_x,
+            position.pos_y,
+            DEFAULT_PLAYER_VIEW_DISTANCE,
+            get_carried_light_radius(&inventory.carried),
+        )
+        .into_iter()
+        .filter(|tile| explored_tiles.insert(*tile))
+        .collect();
+
+        if !newly_explored_tiles.is_empty() {
+            debug!(
+                "Player {} explored {} new tiles on {}.",
+                player_details.player_name,
+                newly_explored_tiles.len(),
+                position.current_map
+            );
+            send_explored_tiles(
+                &position.current_map,
+                newly_explored_tiles,
+                &player_details.client_addr,
+                sender,
+            );
+        }
+    }
+}
+
+pub(super) fn send_explored_maps(
+    explored_maps: &ExploredMaps,
+    client_addr: &str,
+    sender: &Sender<Packet>,
+) {
+    //sent in batches to keep packets small enough however much has been explored
+    for (map, explored_tiles) in &explored_maps.maps {
+        let explored_tiles: Vec<(u32, u32)> = explored_tiles.iter().copied().collect();
+        for batch in explored_tiles.chunks(EXPLORED_TILES_PER_PACKET) {
+            send_explored_tiles(map, batch.to_vec(), client_addr, sender);
+        }
+    }
+    for (map, discovered_traps) in &explored_maps.discovered_traps {
+        send_discovered_traps(
+            map,
+            discovered_traps.iter().copied().collect(),
+            client_addr,
+            sender,
+        );
+    }
+}
+
+pub(super) fn send_explored_tiles(
+    map: &str,
+    explored_tiles: Vec<(u32, u32)>,
+    client_addr: &str,
+    sender: &Sender<Packet>,
+) {
+    let response = encode_to_vec(
+        ServerMessage::UpdateExploredTiles((map.to_string(), explored_tiles)),
+        config::standard(),
+    )
+    .unwrap_or_else(|err| {
+        error!("Failed to encode explored tiles update, error: {err}");
+        process::exit(1);
+    });
+    rustyhack_lib::network::send_packet(
+        Packet::reliable_ordered(client_addr.parse().unwrap(), response, Some(27)),
+        sender,
+    );
+}
+
+pub(super) fn send_discovered_traps(
+    map: &str,
+    discovered_traps: Vec<(u32, u32)>,
+    client_addr: &str,
+    sender: &Sender<Packet>,
+) {
+    let response = encode_to_vec(
+        ServerMessage::UpdateDiscoveredTraps((map.to_string(), discovered_traps)),
+        config::standard(),
+    )
+    .unwrap_or_else(|err| {
+        error!("Failed to encode discovered traps update, error: {err}");
+        process::exit(1);
+    });
+    rustyhack_lib::network::send_packet(
+        Packet::reliable_ordered(client_addr.parse().unwrap(), response, Some(27)),
+        sender,
+    );
+}
+
+pub(super) fn send_tile_changes(
+    map: &str,
+    tiles: Vec<(u32, u32, Tile)>,
+    client_addr: &str,
+    sender: &Sender<Packet>,
+) {
+    let response = encode_to_vec(
+        ServerMessage::UpdateTiles((map.to_string(), tiles)),
+        config::standard(),
+    )
+    .unwrap_or_else(|err| {
+        error!("Failed to encode tile changes update, error: {err}");
+        process::exit(1);
+    });
+    rustyhack_lib::network::send_packet(
+        Packet::reliable_ordered(client_addr.parse().unwrap(), response, Some(28)),
+        sender,
+    );
+}
+
+pub(super) fn send_map_exits(
+    map: &str,
+    map_exits: &[MapExitPositions],
+    client_addr: &str,
+    sender: &Sender<Packet>,
+) {
+    let exits = map_exits
+        .iter()
+        .map(|map_exit| (map_exit.x, map_exit.y, map_exit.new_map.clone()))
+        .collect();
+    let response = encode_to_vec(
+        ServerMessage::UpdateMapExits((map.to_string(), exits)),
+        config::standard(),
+    )
+    .unwrap_or_else(|err| {
+        error!("Failed to encode map exits update, error: {err}");
+        process::exit(1);
+    });
+    rustyhack_lib::network::send_packet(
+        Packet::reliable_ordered(client_addr.parse().unwrap(), response, Some(29)),
+        sender,
+    );
+}
+
+pub(super) fn send_ability_definitions(
+    all_abilities: &AllAbilities,
+    client_addr: &str,
+    sender: &Sender<Packet>,
+) {
+    let response = encode_to_vec(
+        ServerMessage::AbilityDefinitions(all_abilities.clone()),
+        config::standard(),
+    )
+    .unwrap_or_else(|err| {
+        error!("Failed to encode ability definitions, error: {err}");
+        process::exit(1);
+    });
+    //same stream as the joined response so the client always has its player first
+    rustyhack_lib::network::send_packet(
+        Packet::reliable_ordered(client_addr.parse().unwrap(), response, Some(11)),
+        sender,
+    );
+}
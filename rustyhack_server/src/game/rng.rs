@@ -0,0 +1,20 @@
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use uuid::{Builder, Uuid};
+
+//all random rolls in the game go through this resource, so a fixed seed gives repeatable runs
+pub(crate) type GameRng = StdRng;
+
+pub(crate) fn initialise_game_rng(seed: Option<u64>) -> GameRng {
+    if let Some(seed) = seed {
+        info!("Using fixed random seed: {seed}");
+        GameRng::seed_from_u64(seed)
+    } else {
+        GameRng::from_rng(&mut rand::rng())
+    }
+}
+
+//spawned monsters and items take their ids from the game rng too, as ids decide some tie-breaks
+pub(crate) fn new_uuid(rng: &mut GameRng) -> Uuid {
+    Builder::from_random_bytes(rng.random()).into_uuid()
+}
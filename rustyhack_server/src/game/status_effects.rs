@@ -0,0 +1,134 @@
+use crate::consts::{MAX_STATUS_EFFECT_RESISTANCE_PERCENTAGE, STATUS_EFFECT_RESISTANCE_PER_CON};
+use crate::game::rng::GameRng;
+use rand::RngExt;
+use rustyhack_lib::ecs::components::{Stats, StatusEffects};
+use rustyhack_lib::ecs::status_effect::{
+    ActiveStatusEffect, ModifiedStat, OnHitStatusEffect, StackingRule, StatModifier, StatusEffect,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum OnHitResult {
+    Missed,
+    Resisted,
+    Applied,
+}
+
+/*
+Status effect resistance% = Con * Resistance per con, up to the max resistance
+
+Only harmful effects can be resisted.
+*/
+pub(super) fn roll_on_hit_effect(
+    on_hit: &OnHitStatusEffect,
+    status_effects: &mut StatusEffects,
+    stats: &mut Stats,
+    rng: &mut GameRng,
+) -> OnHitResult {
+    if on_hit.chance_percentage < rng.random_range(0.0..=100.0) {
+        return OnHitResult::Missed;
+    }
+    let resistance_percentage =
+        (stats.con * STATUS_EFFECT_RESISTANCE_PER_CON).min(MAX_STATUS_EFFECT_RESISTANCE_PERCENTAGE);
+    if on_hit.effect.harmful && resistance_percentage >= rng.random_range(0.0..=100.0) {
+        return OnHitResult::Resisted;
+    }
+    apply_status_effect(status_effects, stats, &on_hit.effect);
+    OnHitResult::Applied
+}
+
+pub(super) fn apply_status_effect(
+    status_effects: &mut StatusEffects,
+    stats: &mut Stats,
+    effect: &StatusEffect,
+) {
+    status_effects.update_available = true;
+    let Some(existing_effect) = status_effects
+        .active
+        .iter_mut()
+        .find(|active_effect| active_effect.effect.name == effect.name)
+    else {
+        modify_stats(stats, &effect.stat_modifiers, 1.0);
+        status_effects.active.push(ActiveStatusEffect {
+            effect: effect.clone(),
+            remaining_ticks: effect.duration_ticks,
+            stacks: 1,
+        });
+        return;
+    };
+    match effect.stacking {
+        StackingRule::Refresh => existing_effect.remaining_ticks = effect.duration_ticks,
+        StackingRule::Stack { max_stacks } => {
+            if existing_effect.stacks < max_stacks {
+                existing_effect.stacks += 1;
+                modify_stats(stats, &existing_effect.effect.stat_modifiers, 1.0);
+            }
+            existing_effect.remaining_ticks = effect.duration_ticks;
+        }
+        StackingRule::Ignore => {}
+    }
+}
+
+//applies every effect's healing or damage for a tick, then counts them down,
+//taking away and returning any that have worn off along with the total hp change
+pub(super) fn update_status_effects(
+    status_effects: &mut StatusEffects,
+    stats: &mut Stats,
+) -> (f32, Vec<ActiveStatusEffect>) {
+    let hp_change: f32 = status_effects
+        .active
+        .iter()
+        .map(|active_effect| active_effect.effect.hp_per_tick * stacks_as_f32(active_effect))
+        .sum();
+    stats.current_hp = (stats.current_hp + hp_change).min(stats.max_hp);
+    for active_effect in &mut status_effects.active {
+        active_effect.remaining_ticks = active_effect.remaining_ticks.saturating_sub(1);
+    }
+    let (expired_effects, remaining_effects): (Vec<ActiveStatusEffect>, Vec<ActiveStatusEffect>) =
+        status_effects
+            .active
+            .drain(..)
+            .partition(|active_effect| active_effect.remaining_ticks == 0);
+    status_effects.active = remaining_effects;
+    for expired_effect in &expired_effects {
+        modify_stats(
+            stats,
+            &expired_effect.effect.stat_modifiers,
+            -stacks_as_f32(expired_effect),
+        );
+    }
+    status_effects.update_available = true;
+    (hp_change, expired_effects)
+}
+
+pub(super) fn clear_status_effects(status_effects: &mut StatusEffects, stats: &mut Stats) {
+    for active_effect in status_effects.active.drain(..) {
+        modify_stats(
+            stats,
+            &active_effect.effect.stat_modifiers,
+            -stacks_as_f32(&active_effect),
+        );
+    }
+    status_effects.update_available = true;
+}
+
+pub(super) fn is_movement_prevented(status_effects: &StatusEffects) -> bool {
+    status_effects
+        .active
+        .iter()
+        .any(|active_effect| active_effect.effect.prevents_movement)
+}
+
+fn modify_stats(stats: &mut Stats, stat_modifiers: &[StatModifier], multiplier: f32) {
+    for stat_modifier in stat_modifiers {
+        let amount = stat_modifier.amount * multiplier;
+        match stat_modifier.stat {
+            ModifiedStat::Str => stats.str += amount,
+            ModifiedStat::Dex => stats.dex += amount,
+            ModifiedStat::Con => stats.con += amount,
+        }
+    }
+}
+
+fn stacks_as_f32(active_effect: &ActiveStatusEffect) -> f32 {
+    f32::from(u16::try_from(active_effect.stacks).unwrap_or(u16::MAX))
+}
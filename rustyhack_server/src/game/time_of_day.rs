@@ -0,0 +1,27 @@
+use crate::consts::{GAME_DAY_LENGTH, GAME_NIGHT_LENGTH};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/*
+The game clock follows real time so it carries on across server restarts.
+
+Each game day lasts for the day length, and the last part of each day is night.
+*/
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) enum TimeOfDay {
+    Day,
+    Night,
+}
+
+pub(crate) fn get_current_time_of_day() -> TimeOfDay {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let time_into_day = since_epoch.as_secs() % GAME_DAY_LENGTH.as_secs();
+    if time_into_day + GAME_NIGHT_LENGTH.as_secs() >= GAME_DAY_LENGTH.as_secs() {
+        TimeOfDay::Night
+    } else {
+        TimeOfDay::Day
+    }
+}
@@ -33,7 +33,15 @@ fn main() {
     let (sender, receiver) = network_messages::bind_to_socket(&udp_socket_addr);
     let (tcp_handler, tcp_listener) = network_messages::bind_to_tcp_socket(&tcp_socket_addr);
 
-    game::run(&sender, receiver, tcp_handler, tcp_listener);
+    game::run(
+        &sender,
+        receiver,
+        tcp_handler,
+        tcp_listener,
+        setup::is_combat_log_enabled(&args),
+        setup::get_death_penalty_mode(&args),
+        setup::get_rng_seed(&args),
+    );
 
     info!("Program terminated.");
 }
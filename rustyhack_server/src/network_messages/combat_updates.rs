@@ -1,19 +1,34 @@
 use crate::game::combat::{Attacker, Defender};
 use crate::network_messages::send_message_to_player;
+use bincode::{config, encode_to_vec};
 use crossbeam_channel::Sender;
 use crossterm::style::Color;
 use laminar::Packet;
+use rustyhack_lib::network::packets::{AttackOutcome, CombatEvent, ServerMessage};
+use std::process;
 
 pub(crate) fn send_combat_updates_to_players(
     defender: &Defender,
     attacker: &Attacker,
-    damage: f32,
+    combat_event: &CombatEvent,
     current_hp: f32,
     exp_gain: u32,
     gold_gain: u32,
     sender: &Sender<Packet>,
 ) {
-    send_combat_messages_to_players(defender, attacker, damage, current_hp, sender);
+    send_combat_event_to_player(
+        &defender.client_addr,
+        defender.is_player && defender.currently_online,
+        combat_event,
+        sender,
+    );
+    send_combat_event_to_player(
+        &attacker.client_addr,
+        attacker.is_player && attacker.currently_online,
+        combat_event,
+        sender,
+    );
+    send_combat_messages_to_players(defender, attacker, combat_event.outcome, current_hp, sender);
     send_exp_messages_to_players(defender, attacker, current_hp, exp_gain, sender);
     send_gold_messages_to_players(defender, attacker, current_hp, gold_gain, sender);
 }
@@ -82,60 +97,101 @@ fn send_exp_messages_to_players(
     }
 }
 
+//the structured event is used by the client for combat statistics, alongside the usual messages
+fn send_combat_event_to_player(
+    client_addr: &str,
+    should_send: bool,
+    combat_event: &CombatEvent,
+    sender: &Sender<Packet>,
+) {
+    if !should_send || client_addr.is_empty() {
+        return;
+    }
+    let response = encode_to_vec(
+        ServerMessage::CombatEvent(combat_event.clone()),
+        config::standard(),
+    )
+    .unwrap_or_else(|err| {
+        error!("Failed to encode combat event: {combat_event:?}, error: {err}");
+        process::exit(1);
+    });
+    rustyhack_lib::network::send_packet(
+        Packet::reliable_ordered(client_addr.parse().unwrap(), response, Some(31)),
+        sender,
+    );
+}
+
 fn send_combat_messages_to_players(
     defender: &Defender,
     attacker: &Attacker,
-    damage: f32,
+    attack_outcome: AttackOutcome,
     current_hp: f32,
     sender: &Sender<Packet>,
 ) {
-    debug!("Sending combat message to players: {defender:?}, {attacker:?}, {damage}");
-    if damage > 0.0 {
-        if defender.is_player {
-            send_message_to_player(
-                &defender.name,
-                &defender.client_addr,
-                defender.currently_online,
-                &(attacker.name.clone() + " hit you for " + &damage.to_string() + " damage."),
-                Some(Color::DarkRed),
-                sender,
-            );
-        }
-        if attacker.is_player {
-            send_message_to_player(
-                &attacker.name,
-                &attacker.client_addr,
-                attacker.currently_online,
-                &("You hit ".to_string()
-                    + &defender.name
-                    + " for "
-                    + &damage.to_string()
-                    + " damage."),
-                Some(Color::DarkGreen),
-                sender,
-            );
-        }
-    } else {
-        if defender.is_player {
-            send_message_to_player(
-                &defender.name,
-                &defender.client_addr,
-                defender.currently_online,
-                &(attacker.name.clone() + " attacks you, but missed."),
-                Some(Color::Grey),
-                sender,
-            );
-        }
-        if attacker.is_player {
-            send_message_to_player(
-                &attacker.name,
-                &attacker.client_addr,
-                attacker.currently_online,
-                &("You missed your attack against ".to_string() + &defender.name + "."),
-                Some(Color::Grey),
-                sender,
-            );
-        }
+    debug!("Sending combat message to players: {defender:?}, {attacker:?}, {attack_outcome:?}");
+    let (defender_message, attacker_message, defender_colour, attacker_colour) =
+        match attack_outcome {
+            AttackOutcome::Hit { damage, critical } if damage > 0.0 => {
+                let damage_string = damage.to_string();
+                if critical {
+                    (
+                        attacker.name.clone()
+                            + " critically hit you for "
+                            + &damage_string
+                            + " damage!",
+                        "You critically hit ".to_string()
+                            + &defender.name
+                            + " for "
+                            + &damage_string
+                            + " damage!",
+                        Color::Red,
+                        Color::Green,
+                    )
+                } else {
+                    (
+                        attacker.name.clone() + " hit you for " + &damage_string + " damage.",
+                        "You hit ".to_string()
+                            + &defender.name
+                            + " for "
+                            + &damage_string
+                            + " damage.",
+                        Color::DarkRed,
+                        Color::DarkGreen,
+                    )
+                }
+            }
+            AttackOutcome::Blocked => (
+                "You blocked an attack from ".to_string() + &attacker.name + ".",
+                defender.name.clone() + " blocked your attack.",
+                Color::Grey,
+                Color::Grey,
+            ),
+            AttackOutcome::Hit { .. } | AttackOutcome::Missed => (
+                attacker.name.clone() + " attacks you, but missed.",
+                "You missed your attack against ".to_string() + &defender.name + ".",
+                Color::Grey,
+                Color::Grey,
+            ),
+        };
+    if defender.is_player {
+        send_message_to_player(
+            &defender.name,
+            &defender.client_addr,
+            defender.currently_online,
+            &defender_message,
+            Some(defender_colour),
+            sender,
+        );
+    }
+    if attacker.is_player {
+        send_message_to_player(
+            &attacker.name,
+            &attacker.client_addr,
+            attacker.currently_online,
+            &attacker_message,
+            Some(attacker_colour),
+            sender,
+        );
     }
     if current_hp <= 0.0 {
         if defender.is_player {
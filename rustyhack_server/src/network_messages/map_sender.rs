@@ -9,8 +9,12 @@ use std::thread;
 pub(crate) fn spawn_map_sender_thread(
     tcp_handler: NodeHandler<()>,
     tcp_listener: NodeListener<()>,
-    all_maps: AllMaps,
+    mut all_maps: AllMaps,
 ) {
+    //players find traps by searching or stepping on them, so clients aren't told where they are
+    for map in all_maps.values_mut() {
+        map.hide_traps();
+    }
     thread::spawn(move || run(tcp_handler, tcp_listener, &all_maps));
 }
 
@@ -83,6 +83,24 @@ fn handle_player_request(
         PlayerRequest::StatUp(stat_up_details) => {
             send_channel_message(PlayerRequest::StatUp(stat_up_details), channel_sender);
         }
+        PlayerRequest::Search(position_message) => {
+            send_channel_message(PlayerRequest::Search(position_message), channel_sender);
+        }
+        PlayerRequest::BuildBarricade(build_details) => {
+            send_channel_message(PlayerRequest::BuildBarricade(build_details), channel_sender);
+        }
+        PlayerRequest::RangedAttack(ranged_attack_details) => {
+            send_channel_message(
+                PlayerRequest::RangedAttack(ranged_attack_details),
+                channel_sender,
+            );
+        }
+        PlayerRequest::UseAbility(ability_details) => {
+            send_channel_message(PlayerRequest::UseAbility(ability_details), channel_sender);
+        }
+        PlayerRequest::TogglePvp(player_name) => {
+            send_channel_message(PlayerRequest::TogglePvp(player_name), channel_sender);
+        }
         PlayerRequest::GetAllMaps => {
             warn!("Ignoring unexpected GetAllMaps request on udp port.");
         }
@@ -1,4 +1,5 @@
 use crate::consts;
+use crate::game::players::DeathPenaltyMode;
 use rustyhack_lib::utils::file;
 use simplelog::{
     ColorChoice, CombinedLogger, Config, LevelFilter, TermLogger, TerminalMode, WriteLogger,
@@ -9,7 +10,7 @@ use std::{io, process};
 
 pub(super) fn initialise_log(args: &[String]) {
     let mut log_level = LevelFilter::Info;
-    if args.len() > 1 && args[1] == "--debug" {
+    if args.iter().skip(1).any(|arg| arg == "--debug") {
         println!("Debug logging enabled.");
         log_level = LevelFilter::Debug;
     }
@@ -38,6 +39,41 @@ pub(super) fn initialise_log(args: &[String]) {
     });
 }
 
+pub(super) fn is_combat_log_enabled(args: &[String]) -> bool {
+    let combat_log_enabled = args.iter().skip(1).any(|arg| arg == "--combat-log");
+    if combat_log_enabled {
+        println!("Combat log enabled.");
+    }
+    combat_log_enabled
+}
+
+pub(super) fn get_death_penalty_mode(args: &[String]) -> DeathPenaltyMode {
+    if args
+        .iter()
+        .skip(1)
+        .any(|arg| arg == "--corpse-death-penalty")
+    {
+        println!("Corpse death penalty enabled.");
+        DeathPenaltyMode::Corpse
+    } else {
+        DeathPenaltyMode::ExpLoss
+    }
+}
+
+pub(super) fn get_rng_seed(args: &[String]) -> Option<u64> {
+    let seed_arg = args
+        .iter()
+        .skip(1)
+        .skip_while(|arg| *arg != "--seed")
+        .nth(1)?;
+    let seed = seed_arg.parse().unwrap_or_else(|err| {
+        eprintln!("Invalid random seed {seed_arg}, must be a positive whole number: {err}");
+        process::exit(1);
+    });
+    println!("Random seed fixed to {seed}.");
+    Some(seed)
+}
+
 pub(super) fn get_server_addr() -> String {
     println!("--Rustyhack MMO Server Setup--");
 